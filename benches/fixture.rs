@@ -0,0 +1,79 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use spekt::Test;
+use std::collections::BTreeMap;
+
+struct Index {
+    entries: BTreeMap<u64, String>,
+}
+
+#[spekt::async_trait]
+impl Test for Index {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        let entries = (0..10_000).map(|key| (key, key.to_string())).collect();
+
+        Ok(Self { entries })
+    }
+}
+
+struct Counter {
+    count: u64,
+}
+
+#[spekt::async_trait]
+impl Test for Counter {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self { count: 1 })
+    }
+}
+
+fn lookups(criterion: &mut Criterion) {
+    spekt::criterion::bench_fixture::<Index, _, _>(
+        criterion,
+        "index lookups",
+        |index| async move {
+            for key in (0..10_000).step_by(100) {
+                spekt::ensure!(index.entries.contains_key(&key));
+            }
+
+            Ok(())
+        },
+    );
+}
+
+fn lifecycles(criterion: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let mut group = criterion.benchmark_group("lifecycle");
+
+    group.bench_function("arc", |bencher| {
+        bencher.iter(|| {
+            runtime.block_on(Counter::test(|counter| async move {
+                spekt::ensure!(counter.count == 1);
+
+                Ok(())
+            }))
+        })
+    });
+
+    group.bench_function("ref", |bencher| {
+        bencher.iter(|| {
+            runtime.block_on(Counter::test_ref(|counter| {
+                Box::pin(async move {
+                    spekt::ensure!(counter.count == 1);
+
+                    Ok(())
+                })
+            }))
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, lookups, lifecycles);
+criterion_main!(benches);
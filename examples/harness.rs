@@ -0,0 +1,38 @@
+//! Run with `cargo run --example harness --features harness -- --test-threads=2 [FILTER]`
+
+use spekt::{harness::Harness, Test};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Counter {
+    count: AtomicUsize,
+}
+
+#[spekt::async_trait]
+impl Test for Counter {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self {
+            count: AtomicUsize::new(0),
+        })
+    }
+}
+
+fn main() {
+    let mut harness = Harness::new();
+
+    harness
+        .register::<Counter, _, _>("counter::starts_at_zero", |counter| async move {
+            spekt::assert_eq_ok!(counter.count.load(Ordering::SeqCst), 0);
+
+            Ok(())
+        })
+        .register::<Counter, _, _>("counter::increments", |counter| async move {
+            counter.count.fetch_add(1, Ordering::SeqCst);
+            spekt::assert_eq_ok!(counter.count.load(Ordering::SeqCst), 1);
+
+            Ok(())
+        });
+
+    harness.run_main()
+}
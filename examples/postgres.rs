@@ -0,0 +1,42 @@
+//! Run with `cargo run --example postgres --features testcontainers` (requires a running Docker daemon)
+
+use spekt::{Container, Test};
+use std::borrow::Cow;
+use testcontainers::{core::WaitFor, Image};
+
+#[derive(Default)]
+struct Postgres;
+
+impl Image for Postgres {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+
+    fn tag(&self) -> &str {
+        "16-alpine"
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        vec![WaitFor::message_on_stderr(
+            "database system is ready to accept connections",
+        )]
+    }
+
+    fn env_vars(
+        &self,
+    ) -> impl IntoIterator<Item = (impl Into<Cow<'_, str>>, impl Into<Cow<'_, str>>)> {
+        [("POSTGRES_PASSWORD", "postgres")]
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    Container::<Postgres>::test(|postgres| async move {
+        let url = postgres.url("postgres", 5432).await?;
+
+        println!("postgres is listening at {}", url);
+
+        Ok(())
+    })
+    .await;
+}
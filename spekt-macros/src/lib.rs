@@ -0,0 +1,310 @@
+/*!
+Procedural macros for [`spekt`](https://docs.rs/spekt), re-exported from there behind the default `macros` feature.
+*/
+#![deny(missing_docs, unreachable_pub)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::Parser, punctuated::Punctuated, spanned::Spanned, DeriveInput, Error, Expr, ExprLit,
+    FnArg, GenericArgument, ItemFn, Lit, LitStr, MetaNameValue, Path, PathArguments, Token, Type,
+};
+
+/// Arguments accepted by `#[spekt::test(...)]`
+#[derive(Default)]
+struct Args {
+    timeout: Option<TokenStream2>,
+    retries: Option<TokenStream2>,
+}
+
+impl Args {
+    fn parse(tokens: TokenStream) -> Result<Self, Error> {
+        let mut args = Self::default();
+        let pairs = Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse(tokens)?;
+
+        for pair in pairs {
+            let lit = match &pair.value {
+                Expr::Lit(ExprLit { lit, .. }) => lit,
+                value => return Err(Error::new_spanned(value, "expected a literal")),
+            };
+
+            if pair.path.is_ident("timeout") {
+                let timeout = match lit {
+                    Lit::Str(timeout) => parse_duration(&timeout.value(), timeout.span())?,
+                    lit => {
+                        return Err(Error::new_spanned(
+                            lit,
+                            "expected a duration like \"500ms\", \"5s\", or \"1m\"",
+                        ))
+                    }
+                };
+
+                args.timeout = Some(timeout);
+            } else if pair.path.is_ident("retries") {
+                let retries = match lit {
+                    Lit::Int(retries) => retries.base10_parse::<usize>()?,
+                    lit => return Err(Error::new_spanned(lit, "expected a number of attempts")),
+                };
+
+                // test_with_retries counts every attempt, including the first
+                let attempts = retries + 1;
+
+                args.retries = Some(quote!(#attempts));
+            } else {
+                return Err(Error::new_spanned(
+                    &pair.path,
+                    "unknown argument, expected `timeout` or `retries`",
+                ));
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+/// Parse a duration literal like "500ms", "5s", or "1m" into a std::time::Duration expression
+fn parse_duration(value: &str, span: Span) -> Result<TokenStream2, Error> {
+    let split = value
+        .find(|character: char| !character.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| {
+        Error::new(
+            span,
+            "expected a duration like \"500ms\", \"5s\", or \"1m\"",
+        )
+    })?;
+
+    match unit {
+        "ms" => Ok(quote!(::std::time::Duration::from_millis(#amount))),
+        "s" => Ok(quote!(::std::time::Duration::from_secs(#amount))),
+        "m" => Ok(quote!(::std::time::Duration::from_secs(#amount * 60))),
+        _ => Err(Error::new(
+            span,
+            "unknown duration unit, expected `ms`, `s`, or `m`",
+        )),
+    }
+}
+
+/// Check that a test is async, pulling the fixture type out of its single `Arc<Fixture>` argument
+fn fixture(function: &ItemFn) -> Result<&Type, Error> {
+    if function.sig.asyncness.is_none() {
+        return Err(Error::new_spanned(
+            function.sig.fn_token,
+            "the `async` keyword is missing from the function declaration",
+        ));
+    }
+
+    let inputs = &function.sig.inputs;
+    let argument = match inputs.first() {
+        Some(FnArg::Typed(argument)) if inputs.len() == 1 => argument,
+        _ => {
+            return Err(Error::new(
+                function.sig.paren_token.span.join(),
+                "expected a single `Arc<Fixture>` argument",
+            ))
+        }
+    };
+
+    let segment = match &*argument.ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last(),
+        _ => None,
+    };
+
+    let arguments = match segment {
+        Some(segment) if segment.ident == "Arc" => &segment.arguments,
+        _ => {
+            return Err(Error::new_spanned(
+                &argument.ty,
+                "expected an `Arc<Fixture>` argument",
+            ))
+        }
+    };
+
+    match arguments {
+        PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            match arguments.args.first() {
+                Some(GenericArgument::Type(fixture)) => Ok(fixture),
+                _ => Err(Error::new_spanned(arguments, "expected a fixture type")),
+            }
+        }
+        _ => Err(Error::new_spanned(
+            &argument.ty,
+            "expected an `Arc<Fixture>` argument",
+        )),
+    }
+}
+
+/// Wrap a test function in a `#[tokio::test]` that runs it through its fixture's lifecycle
+fn expand(args: Args, mut function: ItemFn) -> Result<TokenStream2, Error> {
+    let fixture = fixture(&function)?.clone();
+    let attrs = std::mem::take(&mut function.attrs);
+    let vis = function.vis.clone();
+    let name = function.sig.ident.clone();
+    function.vis = syn::Visibility::Inherited;
+
+    let body = &function.sig.ident;
+    let call = match (args.timeout, args.retries) {
+        (None, None) => quote!(<#fixture as ::spekt::Test>::test(#body).await),
+        (Some(timeout), None) => {
+            quote!(<#fixture as ::spekt::Test>::test_with_timeout(#timeout, #body).await)
+        }
+        (None, Some(retries)) => {
+            quote!(<#fixture as ::spekt::Test>::test_with_retries(#retries, #body).await)
+        }
+        (Some(timeout), Some(retries)) => quote! {
+            <#fixture as ::spekt::Test>::test_with_retries(#retries, |context| async move {
+                match ::spekt::timeout(#timeout, #body(context)).await {
+                    Ok(output) => output,
+                    Err(error) => Err(::std::convert::From::from(error)),
+                }
+            })
+            .await
+        },
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #[::tokio::test]
+        #vis async fn #name() {
+            #function
+
+            #call
+        }
+    })
+}
+
+/// Turn an `async fn(Arc<Fixture>) -> Result<(), Fixture::Error>` into a `#[tokio::test]` that runs
+/// it through the fixture's `before` -> test -> `after` lifecycle.
+///
+/// Accepts an optional `timeout = "5s"` (with `ms`, `s`, or `m` units) and `retries = 3` extra attempts. When both
+/// are given, the timeout applies to each attempt and the fixture's error type must implement
+/// `From<spekt::TimeoutError>`.
+#[proc_macro_attribute]
+pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
+    let function = syn::parse_macro_input!(item as ItemFn);
+
+    Args::parse(args)
+        .and_then(|args| expand(args, function))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Register a test function with the linked binary's collection of tests
+fn register_test(function: ItemFn) -> Result<TokenStream2, Error> {
+    let fixture = fixture(&function)?;
+    let name = &function.sig.ident;
+
+    Ok(quote! {
+        #function
+
+        const _: () = {
+            fn run() -> ::spekt::BoxFuture<'static, ::spekt::TestOutcome<::std::string::String>> {
+                ::std::boxed::Box::pin(async {
+                    <#fixture as ::spekt::Test>::run(#name)
+                        .await
+                        .map_failure(|error| <#fixture as ::spekt::Test>::fmt_failure(&error))
+                })
+            }
+
+            ::spekt::inventory::submit! {
+                ::spekt::CollectedTest::new(
+                    ::std::concat!(::std::module_path!(), "::", ::std::stringify!(#name)),
+                    run,
+                )
+            }
+        };
+    })
+}
+
+/// Register an `async fn(Arc<Fixture>) -> Result<(), Fixture::Error>` at link time, making it discoverable
+/// through `spekt::collected_tests()` without wiring it into a runner by hand
+#[proc_macro_attribute]
+pub fn register(args: TokenStream, item: TokenStream) -> TokenStream {
+    if let Some(argument) = TokenStream2::from(args).into_iter().next() {
+        return Error::new_spanned(argument, "`#[spekt::register]` takes no arguments")
+            .into_compile_error()
+            .into();
+    }
+
+    let function = syn::parse_macro_input!(item as ItemFn);
+
+    register_test(function)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Options accepted by `#[spekt(...)]` on a `#[derive(Test)]` fixture
+#[derive(Default)]
+struct Options {
+    error: Option<Type>,
+    before: Option<Path>,
+}
+
+impl Options {
+    fn parse(input: &DeriveInput) -> Result<Self, Error> {
+        let mut options = Self::default();
+
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("spekt"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("error") {
+                    options.error = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else if meta.path.is_ident("before") {
+                    options.before = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unknown option, expected `error` or `before`"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(options)
+    }
+}
+
+/// Implement `Test` for a fixture with nothing to tear down
+fn derive(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let options = Options::parse(&input)?;
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let error = options
+        .error
+        .map(|error| quote!(#error))
+        .unwrap_or_else(|| quote!(::anyhow::Error));
+    let before = match options.before {
+        Some(before) => quote_spanned!(before.span()=> #before().await),
+        None => quote_spanned!(name.span()=> Ok(<Self as ::std::default::Default>::default())),
+    };
+
+    Ok(quote! {
+        #[::spekt::async_trait]
+        impl #impl_generics ::spekt::Test for #name #type_generics #where_clause {
+            type Error = #error;
+
+            async fn before() -> ::std::result::Result<Self, Self::Error> {
+                #before
+            }
+        }
+    })
+}
+
+/// Implement `Test` for a fixture that is built in `before` and has nothing to clean up in `after`.
+///
+/// By default the fixture is built with `Default::default()` and its `Test::Error` is `anyhow::Error`,
+/// so crates deriving `Test` without naming an error must depend on `anyhow` themselves.
+/// `#[spekt(error = "MyError")]` picks a different error type, and `#[spekt(before = path::to_fn)]`
+/// builds the fixture with an `async fn() -> Result<Self, Self::Error>` instead.
+#[proc_macro_derive(Test, attributes(spekt))]
+pub fn derive_test(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    derive(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,436 @@
+use std::{
+    any,
+    cell::Cell,
+    error::Error,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    ops::Deref,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+thread_local! {
+    static ASSERTIONS: Cell<Option<Arc<AtomicUsize>>> = const { Cell::new(None) };
+}
+
+/// Count an assertion made by the task currently being polled, if it runs inside a test lifecycle
+#[doc(hidden)]
+pub fn __record_assertion() {
+    ASSERTIONS.with(|assertions| {
+        let counter = assertions.take();
+
+        if let Some(counter) = &counter {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+
+        assertions.set(counter);
+    });
+}
+
+/// Guard restoring the assertion counter of an outer task once an inner task is done being polled
+struct Restore(Option<Arc<AtomicUsize>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        ASSERTIONS.with(|assertions| assertions.set(self.0.take()));
+    }
+}
+
+/// Future that counts every assertion made while polling a task
+pub(crate) struct Counted<F> {
+    future: Pin<Box<F>>,
+    counter: Arc<AtomicUsize>,
+}
+
+impl<F: Future> Future for Counted<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let outer =
+            ASSERTIONS.with(|assertions| assertions.replace(Some(Arc::clone(&self.counter))));
+        let _restore = Restore(outer);
+
+        self.future.as_mut().poll(context)
+    }
+}
+
+/// Count the assertions made by a task in the counter
+pub(crate) fn counted<F>(counter: Arc<AtomicUsize>, future: F) -> Counted<F> {
+    Counted {
+        future: Box::pin(future),
+        counter,
+    }
+}
+
+/// Error raised by `ensure!`, `assert_eq_ok!`, `assert_err_is!`, and `bail!`,
+/// converted into a test's own error with `From`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssertionError {
+    message: String,
+}
+
+impl AssertionError {
+    /// Create an assertion error with a failure message
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    /// The failure message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for AssertionError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.message)
+    }
+}
+
+impl Error for AssertionError {}
+
+/// Return early with an `AssertionError` converted into the task's error type
+///
+/// ```
+/// use spekt::{bail, Test};
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// let result = std::panic::catch_unwind(|| {
+///     Fixture::test_blocking(|_| async move {
+///         bail!("unsupported platform {}", "wasm");
+///     })
+/// });
+///
+/// assert!(result.is_err());
+/// ```
+#[macro_export]
+macro_rules! bail {
+    ($($message:tt)+) => {
+        return ::core::result::Result::Err(::core::convert::From::from(
+            $crate::AssertionError::new(::std::format!($($message)+)),
+        ))
+    };
+}
+
+/// Return early with an `AssertionError` if a condition doesn't hold, so that `after` still runs
+///
+/// ```
+/// use spekt::{ensure, Test};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// static CLEANED_UP: AtomicBool = AtomicBool::new(false);
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+///
+///     async fn after(&self) -> Result<(), Self::Error> {
+///         CLEANED_UP.store(true, Ordering::SeqCst);
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let result = std::panic::catch_unwind(|| {
+///     Fixture::test_blocking(|_| async move {
+///         ensure!(1 + 1 == 3, "arithmetic is broken");
+///
+///         Ok(())
+///     })
+/// });
+///
+/// assert!(result.is_err());
+/// assert!(CLEANED_UP.load(Ordering::SeqCst));
+/// ```
+#[macro_export]
+macro_rules! ensure {
+    ($condition:expr $(,)?) => {{
+        $crate::__record_assertion();
+
+        if !$condition {
+            $crate::bail!("condition failed: `{}`", ::core::stringify!($condition));
+        }
+    }};
+    ($condition:expr, $($message:tt)+) => {{
+        $crate::__record_assertion();
+
+        if !$condition {
+            $crate::bail!($($message)+);
+        }
+    }};
+}
+
+/// Return early with an `AssertionError` if two values are not equal, so that `after` still runs
+#[macro_export]
+macro_rules! assert_eq_ok {
+    ($left:expr, $right:expr $(,)?) => {{
+        $crate::__record_assertion();
+
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left == *right) {
+                    $crate::bail!("assertion `left == right` failed: {:?} != {:?}", left, right);
+                }
+            }
+        }
+    }};
+    ($left:expr, $right:expr, $($message:tt)+) => {{
+        $crate::__record_assertion();
+
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left == *right) {
+                    $crate::bail!(
+                        "assertion `left == right` failed: {:?} != {:?}: {}",
+                        left,
+                        right,
+                        ::std::format!($($message)+)
+                    );
+                }
+            }
+        }
+    }};
+}
+
+/// Check that a result failed with an error of type `E`, anywhere in its source chain,
+/// returning the error for further assertions. Works with any error that is `AsRef<dyn Error>`,
+/// like `anyhow::Error` or `Box<dyn Error + Send + Sync>`
+///
+/// ```
+/// use std::io;
+///
+/// let result: Result<(), anyhow::Error> = Err(io::Error::from(io::ErrorKind::NotFound).into());
+///
+/// let error = spekt::expect_err::<io::Error>(&result).unwrap();
+/// assert_eq!(error.kind(), io::ErrorKind::NotFound);
+///
+/// assert!(spekt::expect_err::<std::fmt::Error>(&result).is_err());
+/// assert!(spekt::expect_err::<io::Error>(&Ok::<_, anyhow::Error>(())).is_err());
+/// ```
+pub fn expect_err<E: Error + 'static>(
+    result: &Result<impl Sized, impl AsRef<dyn Error + Send + Sync + 'static>>,
+) -> Result<&E, AssertionError> {
+    let error = match result {
+        Ok(_) => {
+            return Err(AssertionError::new(format!(
+                "expected an error of type `{}`, but got Ok",
+                any::type_name::<E>()
+            )))
+        }
+        Err(error) => error.as_ref(),
+    };
+
+    let mut source: Option<&(dyn Error + 'static)> = Some(error);
+
+    while let Some(cause) = source {
+        if let Some(error) = cause.downcast_ref::<E>() {
+            return Ok(error);
+        }
+
+        source = cause.source();
+    }
+
+    Err(AssertionError::new(format!(
+        "expected an error of type `{}`, but got: {}",
+        any::type_name::<E>(),
+        error
+    )))
+}
+
+/// Error a result failed with, checked by `take_err` to hold an error of type `E` anywhere in its source chain,
+/// and dereferencing to that error. Owns the whole error, so it outlives the result it was taken from
+pub struct ErrIs<X, E> {
+    error: X,
+    downcast: PhantomData<fn() -> E>,
+}
+
+impl<X, E> ErrIs<X, E> {
+    /// The whole error, including every cause above the downcast one
+    pub fn into_inner(self) -> X {
+        self.error
+    }
+}
+
+impl<X, E> Deref for ErrIs<X, E>
+where
+    X: AsRef<dyn Error + Send + Sync + 'static>,
+    E: Error + 'static,
+{
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        let mut source: Option<&(dyn Error + 'static)> = Some(self.error.as_ref());
+
+        while let Some(cause) = source {
+            if let Some(error) = cause.downcast_ref::<E>() {
+                return error;
+            }
+
+            source = cause.source();
+        }
+
+        unreachable!("checked to hold the error when taken")
+    }
+}
+
+impl<X, E> fmt::Debug for ErrIs<X, E>
+where
+    X: AsRef<dyn Error + Send + Sync + 'static>,
+    E: Error + 'static,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, formatter)
+    }
+}
+
+/// Check that a result failed with an error of type `E`, anywhere in its source chain, like `expect_err`,
+/// but taking the result by value, so that the error can be kept even if the result was a temporary
+///
+/// ```
+/// use std::io;
+///
+/// fn open() -> Result<(), anyhow::Error> {
+///     Err(io::Error::from(io::ErrorKind::NotFound).into())
+/// }
+///
+/// let error = spekt::take_err::<io::Error, _>(open()).unwrap();
+/// assert_eq!(error.kind(), io::ErrorKind::NotFound);
+///
+/// assert!(spekt::take_err::<std::fmt::Error, _>(open()).is_err());
+/// ```
+pub fn take_err<E, X>(result: Result<impl Sized, X>) -> Result<ErrIs<X, E>, AssertionError>
+where
+    E: Error + 'static,
+    X: AsRef<dyn Error + Send + Sync + 'static>,
+{
+    expect_err::<E>(&result)?;
+
+    match result {
+        Ok(_) => unreachable!("checked to be an error"),
+        Err(error) => Ok(ErrIs {
+            error,
+            downcast: PhantomData,
+        }),
+    }
+}
+
+/// Check that each successive error in a source chain contains the expected message, starting from the error
+/// itself, returning the leaf of the chain for further assertions. Works with any error that is
+/// `AsRef<dyn Error>`, like `anyhow::Error` or `Box<dyn Error + Send + Sync>`
+///
+/// ```
+/// use anyhow::Context;
+/// use std::io;
+///
+/// let error = Err::<(), _>(io::Error::new(io::ErrorKind::NotFound, "no such table"))
+///     .context("failed to migrate")
+///     .unwrap_err();
+///
+/// let leaf = spekt::assert_causes(&error, &["migrate", "no such table"]).unwrap();
+/// assert!(leaf.downcast_ref::<io::Error>().is_some());
+///
+/// assert!(spekt::assert_causes(&error, &["migrate", "connection refused"]).is_err());
+/// assert!(spekt::assert_causes(&error, &["migrate", "no such table", "disk"]).is_err());
+/// ```
+pub fn assert_causes<'a>(
+    error: &'a impl AsRef<dyn Error + Send + Sync + 'static>,
+    expected: &[&str],
+) -> Result<&'a (dyn Error + 'static), AssertionError> {
+    __record_assertion();
+
+    let mut cause: &(dyn Error + 'static) = error.as_ref();
+
+    for (depth, message) in expected.iter().enumerate() {
+        if depth > 0 {
+            cause = cause.source().ok_or_else(|| {
+                AssertionError::new(format!(
+                    "expected cause {} to contain {:?}, but the chain ends after {} errors",
+                    depth, message, depth
+                ))
+            })?;
+        }
+
+        let actual = cause.to_string();
+
+        if !actual.contains(message) {
+            return Err(AssertionError::new(format!(
+                "expected cause {} to contain {:?}, but got: {}",
+                depth, message, actual
+            )));
+        }
+    }
+
+    while let Some(source) = cause.source() {
+        cause = source;
+    }
+
+    Ok(cause)
+}
+
+/// Return early with an `AssertionError` unless a result failed with an error of the given type,
+/// evaluating to an `ErrIs` that dereferences to the downcast error otherwise. The result is taken by value,
+/// so it may be a temporary like the return value of a call
+///
+/// ```
+/// use spekt::{assert_err_is, Test};
+/// use std::num::ParseIntError;
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// fn parse(digits: &str) -> anyhow::Result<u8> {
+///     Ok(digits.parse()?)
+/// }
+///
+/// Fixture::test_blocking(|_| async move {
+///     let error = assert_err_is!(parse("two"), ParseIntError);
+///
+///     assert_eq!(error.to_string(), "invalid digit found in string");
+///
+///     Ok(())
+/// });
+/// ```
+#[macro_export]
+macro_rules! assert_err_is {
+    ($result:expr, $error:ty $(,)?) => {{
+        $crate::__record_assertion();
+
+        match $crate::take_err::<$error, _>($result) {
+            ::core::result::Result::Ok(error) => error,
+            ::core::result::Result::Err(failure) => {
+                return ::core::result::Result::Err(::core::convert::From::from(failure))
+            }
+        }
+    }};
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+/// Summary of the steady-state durations measured by `Test::bench`
+///
+/// ```
+/// use spekt::Test;
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let stats = Fixture::bench(100, 10, |_| async {
+///     std::hint::black_box((0..1_000).sum::<u64>());
+///
+///     Ok(())
+/// })
+/// .await;
+///
+/// assert!(stats.min <= stats.p50 && stats.p50 <= stats.p99 && stats.p99 <= stats.max);
+/// assert!(stats.min <= stats.mean && stats.mean <= stats.max);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BenchStats {
+    /// Fastest iteration
+    pub min: Duration,
+    /// Slowest iteration
+    pub max: Duration,
+    /// Average iteration
+    pub mean: Duration,
+    /// Median iteration
+    pub p50: Duration,
+    /// 99th percentile iteration
+    pub p99: Duration,
+}
+
+impl BenchStats {
+    /// Summarize the duration of each measured iteration
+    pub(crate) fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        samples.sort();
+
+        let total: Duration = samples.iter().sum();
+        let percentile = |percent: usize| {
+            let rank = (samples.len() * percent).div_ceil(100);
+
+            samples[rank.saturating_sub(1)]
+        };
+
+        Self {
+            min: samples[0],
+            max: samples[samples.len() - 1],
+            mean: total / samples.len() as u32,
+            p50: percentile(50),
+            p99: percentile(99),
+        }
+    }
+}
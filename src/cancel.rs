@@ -0,0 +1,132 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+/// Shared flag and the wakers of every task waiting on it
+#[derive(Default)]
+struct Signal {
+    cancelled: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+/// Cloneable signal for background tasks spawned by a test, cancelled automatically once the task finishes
+/// and before its deferred clean-up and after() run
+///
+/// ```
+/// use spekt::Test;
+/// use std::sync::{
+///     atomic::{AtomicBool, Ordering},
+///     Arc,
+/// };
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let stopped = Arc::new(AtomicBool::new(false));
+/// let background = Arc::new(std::sync::Mutex::new(None));
+/// let (flag, handle) = (Arc::clone(&stopped), Arc::clone(&background));
+///
+/// Fixture::test_with_context(|context| async move {
+///     let token = context.cancel_token().clone();
+///
+///     *handle.lock().unwrap() = Some(tokio::spawn(async move {
+///         token.cancelled().await;
+///         flag.store(true, Ordering::SeqCst);
+///     }));
+///
+///     Ok(())
+/// })
+/// .await;
+///
+/// let background = background.lock().unwrap().take().unwrap();
+///
+/// background.await.unwrap();
+/// assert!(stopped.load(Ordering::SeqCst));
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct CancelToken {
+    signal: Arc<Signal>,
+}
+
+impl CancelToken {
+    /// Create a token that hasn't been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the token, waking every task waiting on it
+    pub fn cancel(&self) {
+        self.signal.cancelled.store(true, Ordering::SeqCst);
+
+        for waker in self.signal.wakers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+
+    /// Check if the token has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.signal.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the token is cancelled
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            signal: Arc::clone(&self.signal),
+        }
+    }
+}
+
+impl fmt::Debug for CancelToken {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("CancelToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Future that resolves once its token is cancelled
+pub struct Cancelled {
+    signal: Arc<Signal>,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.signal.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+
+        let mut wakers = self.signal.wakers.lock().unwrap();
+
+        // the token may have been cancelled while the wakers were locked by cancel()
+        if self.signal.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+
+        if !wakers.iter().any(|waker| waker.will_wake(context.waker())) {
+            wakers.push(context.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
@@ -0,0 +1,73 @@
+use gag::BufferRedirect;
+use std::io::{self, Read, Write};
+
+/// Redirection of the process's stdout and stderr into buffers, restored once finished or dropped.
+/// Streams that are already redirected elsewhere (e.g. by a concurrent test) are left uncaptured
+pub(crate) struct Capture {
+    stdout: Option<BufferRedirect>,
+    stderr: Option<BufferRedirect>,
+}
+
+impl Capture {
+    /// Start redirecting both streams
+    pub(crate) fn start() -> Self {
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+
+        Self {
+            stdout: BufferRedirect::stdout().ok(),
+            stderr: BufferRedirect::stderr().ok(),
+        }
+    }
+
+    /// Restore both streams, returning everything written to them while redirected
+    pub(crate) fn finish(self) -> Captured {
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+
+        Captured {
+            stdout: read(self.stdout),
+            stderr: read(self.stderr),
+        }
+    }
+}
+
+/// Drain a redirected stream's buffer, restoring the stream
+fn read(redirect: Option<BufferRedirect>) -> String {
+    let mut output = String::new();
+
+    if let Some(mut redirect) = redirect {
+        let _ = redirect.read_to_string(&mut output);
+    }
+
+    output
+}
+
+/// Output written to stdout and stderr while captured
+pub(crate) struct Captured {
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
+}
+
+impl Captured {
+    /// Write the output back to the streams it was captured from
+    pub(crate) fn replay(&self) {
+        print!("{}", self.stdout);
+        eprint!("{}", self.stderr);
+    }
+
+    /// Append the output to a failure message, labeling each non-empty stream
+    pub(crate) fn append_to(&self, mut message: String) -> String {
+        for (stream, output) in [("stdout", &self.stdout), ("stderr", &self.stderr)] {
+            if !output.is_empty() {
+                message.push_str(&format!(
+                    "\n\n---- captured {} ----\n{}",
+                    stream,
+                    output.trim_end()
+                ));
+            }
+        }
+
+        message
+    }
+}
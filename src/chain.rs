@@ -0,0 +1,111 @@
+use crate::{
+    lifecycle,
+    test::{BoxFuture, Test},
+};
+use std::{any::Any, fmt, future::Future, sync::Arc};
+
+/// Every step of a chain composed so far, producing the output of the last one
+type Steps<S, O> =
+    Box<dyn FnOnce(Arc<S>) -> BoxFuture<'static, Result<O, <S as Test>::Error>> + Send + Sync>;
+
+/// Sequence of dependent test steps (like create -> update -> delete) sharing one lifecycle, where each step
+/// receives the value returned by the previous one. The first error skips the remaining steps, but after still
+/// runs before the chain fails.
+///
+/// ```
+/// use spekt::{Chain, Test};
+///
+/// struct Api;
+///
+/// #[spekt::async_trait]
+/// impl Test for Api {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let deleted = Chain::<Api, _>::new()
+///     .then(|_api, ()| async { Ok("alice".to_string()) })
+///     .then(|_api, name| async move { Ok((name, 2)) })
+///     .then(|_api, (name, version)| async move {
+///         anyhow::ensure!(version == 2, "{} was not updated", name);
+///
+///         Ok(name)
+///     })
+///     .test()
+///     .await;
+///
+/// assert_eq!(deleted.as_deref(), Some("alice"));
+/// # }
+/// ```
+pub struct Chain<S: Test, O> {
+    steps: Steps<S, O>,
+}
+
+impl<S: Test + 'static> Chain<S, ()> {
+    /// Create a chain without any steps
+    pub fn new() -> Self {
+        Self {
+            steps: Box::new(|_| Box::pin(async { Ok(()) })),
+        }
+    }
+}
+
+impl<S: Test + 'static> Default for Chain<S, ()> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, O> Chain<S, O>
+where
+    S: Test + 'static,
+    O: Send + 'static,
+{
+    /// Add a step that receives the state and the value returned by the previous step
+    pub fn then<N, F, T>(self, step: T) -> Chain<S, N>
+    where
+        F: Future<Output = Result<N, S::Error>> + Send + 'static,
+        T: Send + Sync + 'static + FnOnce(Arc<S>, O) -> F,
+    {
+        let steps = self.steps;
+
+        Chain {
+            steps: Box::new(move |state: Arc<S>| {
+                Box::pin(async move {
+                    let output = steps(Arc::clone(&state)).await?;
+
+                    step(state, output).await
+                })
+            }),
+        }
+    }
+
+    /// Run every step in order, returning the output of the last one (or nothing if before skipped the test)
+    /// or the first error encountered
+    pub async fn try_test(self) -> Result<Option<O>, S::Error>
+    where
+        O: Any + Sync,
+    {
+        lifecycle::try_run_returning::<S, _, _, _>(self.steps).await
+    }
+
+    /// Run every step in order, handling assertion errors gracefully and returning the output of the last one,
+    /// or nothing if before skipped the test
+    pub async fn test(self) -> Option<O>
+    where
+        O: Any + Sync,
+    {
+        S::test_returning(self.steps).await
+    }
+}
+
+impl<S: Test, O> fmt::Debug for Chain<S, O> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_struct("Chain").finish_non_exhaustive()
+    }
+}
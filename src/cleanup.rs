@@ -0,0 +1,72 @@
+use crate::test::BoxFuture;
+use std::{fmt, future::Future};
+
+type Action<E> = Box<dyn FnOnce() -> BoxFuture<'static, Result<(), E>> + Send>;
+
+/// Stack of async clean-up actions registered incrementally during setup
+pub struct CleanupStack<E> {
+    actions: Vec<Action<E>>,
+}
+
+impl<E> CleanupStack<E> {
+    /// Create an empty clean-up stack
+    pub fn new() -> Self {
+        Self {
+            actions: Vec::new(),
+        }
+    }
+
+    /// Register a clean-up action to run once setup is torn down
+    pub fn defer<C, F>(&mut self, cleanup: C)
+    where
+        C: FnOnce() -> F + Send + 'static,
+        F: Future<Output = Result<(), E>> + Send + 'static,
+    {
+        self.actions.push(Box::new(move || Box::pin(cleanup())));
+    }
+
+    /// Move every action from another stack on top of this one, so that they run first
+    pub fn append(&mut self, mut other: Self) {
+        self.actions.append(&mut other.actions);
+    }
+
+    /// Number of registered clean-up actions
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Check if any clean-up actions have been registered
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Run every registered action in reverse registration order, returning the first error
+    pub async fn run(self) -> Result<(), E> {
+        let mut result = Ok(());
+
+        for action in self.actions.into_iter().rev() {
+            let cleanup = action().await;
+
+            if result.is_ok() {
+                result = cleanup;
+            }
+        }
+
+        result
+    }
+}
+
+impl<E> Default for CleanupStack<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> fmt::Debug for CleanupStack<E> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("CleanupStack")
+            .field("actions", &self.actions.len())
+            .finish()
+    }
+}
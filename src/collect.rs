@@ -0,0 +1,72 @@
+use crate::{outcome::TestOutcome, test::BoxFuture};
+use std::fmt;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// Test case registered at link time with `#[spekt::register]`, with failures formatted by its fixture
+pub struct CollectedTest {
+    name: &'static str,
+    run: fn() -> BoxFuture<'static, TestOutcome<String>>,
+}
+
+impl CollectedTest {
+    #[doc(hidden)]
+    pub const fn new(
+        name: &'static str,
+        run: fn() -> BoxFuture<'static, TestOutcome<String>>,
+    ) -> Self {
+        Self { name, run }
+    }
+
+    /// Full path of the registered test function
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Run the test through its fixture's lifecycle, reporting its outcome instead of panicking
+    pub fn run(&self) -> BoxFuture<'static, TestOutcome<String>> {
+        (self.run)()
+    }
+}
+
+impl fmt::Debug for CollectedTest {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("CollectedTest")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+inventory::collect!(CollectedTest);
+
+/// Every test registered with `#[spekt::register]` across the linked binary
+///
+/// ```
+/// use spekt::{Test, TestOutcome};
+/// use std::sync::Arc;
+///
+/// #[derive(Default, Test)]
+/// struct Fixture;
+///
+/// #[spekt::register]
+/// async fn passes(_fixture: Arc<Fixture>) -> Result<(), anyhow::Error> {
+///     Ok(())
+/// }
+///
+/// #[spekt::register]
+/// async fn fails(_fixture: Arc<Fixture>) -> Result<(), anyhow::Error> {
+///     anyhow::bail!("expected failure")
+/// }
+///
+/// let mut names: Vec<_> = spekt::collected_tests().map(|test| test.name()).collect();
+///
+/// names.sort();
+/// assert_eq!(names.len(), 2);
+/// assert!(names[0].ends_with("::fails"));
+/// assert!(names[1].ends_with("::passes"));
+/// ```
+pub fn collected_tests() -> impl Iterator<Item = &'static CollectedTest> {
+    inventory::iter::<CollectedTest>.into_iter()
+}
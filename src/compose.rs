@@ -0,0 +1,380 @@
+use crate::{
+    assert::AssertionError,
+    cleanup::CleanupStack,
+    fork::ForkUnsupported,
+    join::try_join,
+    outcome::{BeforeOutcome, TestOutcome},
+    resource::Resources,
+    test::Test,
+};
+use async_trait::async_trait;
+use std::{any::Any, time::Duration};
+
+/// Keep every error from a sequence of teardowns, combining them with the test's own aggregation
+fn aggregate<S: Test>(
+    first: Result<(), S::Error>,
+    later: Result<(), S::Error>,
+) -> Result<(), S::Error> {
+    match (first, later) {
+        (Err(first), Err(later)) => Err(S::combine_errors(first, later)),
+        (first, later) => first.and(later),
+    }
+}
+
+/// Pick the error of whichever setup failed out of a pair that didn't both succeed
+fn setup_error<A, B, E>(first: Option<Result<A, E>>, second: Option<Result<B, E>>) -> E {
+    match (first, second) {
+        (Some(Err(error)), _) | (_, Some(Err(error))) => error,
+        _ => unreachable!("setup of one fixture failed"),
+    }
+}
+
+/// Tear down whichever half of a pair finished setting up while the other failed or skipped, in reverse order
+/// along with the clean-up each registered, ignoring their errors in favor of the setup's
+async fn abandon<A, B>(
+    first: Option<&A>,
+    first_cleanup: CleanupStack<A::Error>,
+    second: Option<&B>,
+    second_cleanup: CleanupStack<A::Error>,
+) where
+    A: Test,
+    B: Test<Error = A::Error>,
+{
+    if let Some(second) = second {
+        let _ = second.after().await;
+    }
+
+    let _ = second_cleanup.run().await;
+
+    if let Some(first) = first {
+        let _ = first.after().await;
+    }
+
+    let _ = first_cleanup.run().await;
+}
+
+/// Get the state of a fixture that finished setting up without skipping
+fn ready<S, E>(outcome: &Option<Result<BeforeOutcome<S>, E>>) -> Option<&S> {
+    match outcome {
+        Some(Ok(BeforeOutcome::Ready(state))) => Some(state),
+        _ => None,
+    }
+}
+
+/// Compose two independent fixtures that share an error type. Both are set up concurrently, then torn down
+/// in reverse order (the second fixture before the first, along with any clean-up they registered), so that
+/// later fixtures can depend on earlier ones. Every teardown runs even if another fails, with failures
+/// combined through `Test::combine_errors` of the first fixture. As soon as either setup fails, the other is
+/// abandoned if it is still running, or torn down if it already finished, before the setup error is returned,
+/// so that a half-built pair never leaks, and if either fixture skips through `Test::before_or_skip`, the pair
+/// is skipped the same way. Every other hook is forwarded to both fixtures, first to second while setting up and
+/// second to first while tearing down, so that pairs nest like any other fixture. Tasks reach each fixture
+/// through the tuple's fields:
+///
+/// ```
+/// use spekt::Test;
+///
+/// struct Database;
+/// struct Broker;
+///
+/// #[spekt::async_trait]
+/// impl Test for Database {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Test for Broker {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// <(Database, Broker)>::test_blocking(|fixtures| async move {
+///     let (_database, _broker) = &*fixtures;
+///
+///     Ok(())
+/// });
+/// ```
+///
+/// If the second fixture fails to start, the first is still torn down:
+///
+/// ```
+/// use spekt::{Test, TestOutcome};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// static DATABASE_DROPPED: AtomicBool = AtomicBool::new(false);
+///
+/// struct Database;
+/// struct Broker;
+///
+/// #[spekt::async_trait]
+/// impl Test for Database {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+///
+///     async fn after(&self) -> Result<(), Self::Error> {
+///         DATABASE_DROPPED.store(true, Ordering::SeqCst);
+///
+///         Ok(())
+///     }
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Test for Broker {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         tokio::task::yield_now().await;
+///
+///         anyhow::bail!("broker refused connection")
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let outcome = <(Database, Broker)>::run(|_| async { Ok(()) }).await;
+///
+/// assert!(matches!(outcome, TestOutcome::Failed(error) if error.to_string() == "broker refused connection"));
+/// assert!(DATABASE_DROPPED.load(Ordering::SeqCst));
+/// # }
+/// ```
+#[async_trait]
+impl<A, B> Test for (A, B)
+where
+    A: Test,
+    B: Test<Error = A::Error>,
+{
+    type Error = A::Error;
+
+    fn fmt_failure(error: &Self::Error) -> String {
+        A::fmt_failure(error)
+    }
+
+    fn combine_errors(first: Self::Error, later: Self::Error) -> Self::Error {
+        A::combine_errors(first, later)
+    }
+
+    fn panic_to_error(payload: &(dyn Any + Send)) -> Option<Self::Error> {
+        A::panic_to_error(payload).or_else(|| B::panic_to_error(payload))
+    }
+
+    fn fail_on_flaky() -> bool {
+        A::fail_on_flaky() || B::fail_on_flaky()
+    }
+
+    fn assertion_error(error: AssertionError) -> Option<Self::Error> {
+        A::assertion_error(error.clone()).or_else(|| B::assertion_error(error))
+    }
+
+    fn warn_slow_before(elapsed: Duration, threshold: Duration) {
+        A::warn_slow_before(elapsed, threshold);
+        B::warn_slow_before(elapsed, threshold);
+    }
+
+    async fn env() -> Result<(), Self::Error> {
+        A::env().await?;
+        B::env().await
+    }
+
+    async fn before() -> Result<Self, Self::Error> {
+        match try_join(A::before(), B::before()).await {
+            (Some(Ok(first)), Some(Ok(second))) => Ok((first, second)),
+            (first, second) => {
+                if let Some(Ok(second)) = &second {
+                    let _ = second.after().await;
+                }
+
+                if let Some(Ok(first)) = &first {
+                    let _ = first.after().await;
+                }
+
+                Err(setup_error(first, second))
+            }
+        }
+    }
+
+    async fn before_with_cleanup(
+        cleanup: &mut CleanupStack<Self::Error>,
+    ) -> Result<Self, Self::Error> {
+        Self::before_with_resources(cleanup, &mut Resources::new()).await
+    }
+
+    async fn before_with_resources(
+        cleanup: &mut CleanupStack<Self::Error>,
+        resources: &mut Resources,
+    ) -> Result<Self, Self::Error> {
+        let mut first_cleanup = CleanupStack::new();
+        let mut second_cleanup = CleanupStack::new();
+        let mut first_resources = Resources::new();
+        let mut second_resources = Resources::new();
+        let (first, second) = try_join(
+            A::before_with_resources(&mut first_cleanup, &mut first_resources),
+            B::before_with_resources(&mut second_cleanup, &mut second_resources),
+        )
+        .await;
+
+        match (first, second) {
+            (Some(Ok(first)), Some(Ok(second))) => {
+                cleanup.append(first_cleanup);
+                cleanup.append(second_cleanup);
+                resources.extend(first_resources);
+                resources.extend(second_resources);
+
+                Ok((first, second))
+            }
+            (first, second) => {
+                abandon(
+                    first.as_ref().and_then(|first| first.as_ref().ok()),
+                    first_cleanup,
+                    second.as_ref().and_then(|second| second.as_ref().ok()),
+                    second_cleanup,
+                )
+                .await;
+
+                Err(setup_error(first, second))
+            }
+        }
+    }
+
+    async fn before_or_skip(
+        cleanup: &mut CleanupStack<Self::Error>,
+        resources: &mut Resources,
+    ) -> Result<BeforeOutcome<Self>, Self::Error> {
+        let mut first_cleanup = CleanupStack::new();
+        let mut second_cleanup = CleanupStack::new();
+        let mut first_resources = Resources::new();
+        let mut second_resources = Resources::new();
+        let (first, second) = try_join(
+            A::before_or_skip(&mut first_cleanup, &mut first_resources),
+            B::before_or_skip(&mut second_cleanup, &mut second_resources),
+        )
+        .await;
+
+        match (first, second) {
+            (Some(Ok(BeforeOutcome::Ready(first))), Some(Ok(BeforeOutcome::Ready(second)))) => {
+                cleanup.append(first_cleanup);
+                cleanup.append(second_cleanup);
+                resources.extend(first_resources);
+                resources.extend(second_resources);
+
+                Ok(BeforeOutcome::Ready((first, second)))
+            }
+            (first, second) => {
+                abandon(ready(&first), first_cleanup, ready(&second), second_cleanup).await;
+
+                match (first, second) {
+                    (Some(Err(error)), _) | (_, Some(Err(error))) => Err(error),
+                    (Some(Ok(BeforeOutcome::Skip(reason))), _)
+                    | (_, Some(Ok(BeforeOutcome::Skip(reason)))) => Ok(BeforeOutcome::Skip(reason)),
+                    _ => unreachable!("setup of one fixture failed or skipped"),
+                }
+            }
+        }
+    }
+    async fn before_each(&self) -> Result<(), Self::Error> {
+        self.0.before_each().await?;
+        self.1.before_each().await
+    }
+
+    async fn after_each(&self) -> Result<(), Self::Error> {
+        let second = self.1.after_each().await;
+        let first = self.0.after_each().await;
+
+        aggregate::<Self>(second, first)
+    }
+
+    async fn enrich_error(&self, error: Self::Error) -> Self::Error {
+        let error = self.0.enrich_error(error).await;
+
+        self.1.enrich_error(error).await
+    }
+
+    async fn before_all() -> Result<(), Self::Error> {
+        A::before_all().await?;
+
+        if let Err(error) = B::before_all().await {
+            let _ = A::after_all().await;
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    async fn after_all() -> Result<(), Self::Error> {
+        let second = B::after_all().await;
+        let first = A::after_all().await;
+
+        aggregate::<Self>(second, first)
+    }
+
+    async fn on_start(name: &str) {
+        A::on_start(name).await;
+        B::on_start(name).await;
+    }
+
+    async fn on_finish(name: &str, outcome: &TestOutcome<Self::Error>) {
+        B::on_finish(name, outcome).await;
+        A::on_finish(name, outcome).await;
+    }
+
+    async fn shutdown(&self) -> Result<(), Self::Error> {
+        let second = self.1.shutdown().await;
+        let first = self.0.shutdown().await;
+
+        aggregate::<Self>(second, first)
+    }
+
+    async fn fork(&self) -> Result<Self, Self::Error>
+    where
+        Self::Error: From<ForkUnsupported>,
+    {
+        let first = self.0.fork().await?;
+
+        match self.1.fork().await {
+            Ok(second) => Ok((first, second)),
+            Err(error) => {
+                let _ = first.after().await;
+
+                Err(error)
+            }
+        }
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        let second = self.1.after().await;
+        let first = self.0.after().await;
+
+        aggregate::<Self>(second, first)
+    }
+
+    async fn after_outcome(&self, outcome: &TestOutcome<Self::Error>) -> Result<(), Self::Error> {
+        let second = self.1.after_outcome(outcome).await;
+        let first = self.0.after_outcome(outcome).await;
+
+        aggregate::<Self>(second, first)
+    }
+
+    async fn after_with<R>(
+        &self,
+        output: Option<&R>,
+        outcome: &TestOutcome<Self::Error>,
+    ) -> Result<(), Self::Error>
+    where
+        R: Any + Send + Sync,
+    {
+        let second = self.1.after_with(output, outcome).await;
+        let first = self.0.after_with(output, outcome).await;
+
+        aggregate::<Self>(second, first)
+    }
+}
@@ -0,0 +1,346 @@
+use crate::outcome::TestOutcome;
+use std::{env, time::Duration};
+
+/// When a test's after() runs, based on the outcome of its task.
+/// Skipping after() on failure leaves resources like databases intact for inspection
+///
+/// ```
+/// use spekt::{AfterPolicy, Test, TestConfig};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// static CLEANED_UP: AtomicBool = AtomicBool::new(false);
+///
+/// struct Database;
+///
+/// #[spekt::async_trait]
+/// impl Test for Database {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+///
+///     async fn after(&self) -> Result<(), Self::Error> {
+///         CLEANED_UP.store(true, Ordering::SeqCst);
+///
+///         Ok(())
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let config = TestConfig {
+///     after_policy: AfterPolicy::OnFailure,
+///     ..TestConfig::default()
+/// };
+///
+/// Database::test_with_config(config, |_| async { Ok(()) }).await;
+///
+/// assert!(!CLEANED_UP.load(Ordering::SeqCst));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AfterPolicy {
+    /// Always run after()
+    #[default]
+    Always,
+    /// Only run after() if the task passed
+    OnSuccess,
+    /// Only run after() if the task failed, timed out or panicked
+    OnFailure,
+    /// Never run after()
+    Never,
+}
+
+impl AfterPolicy {
+    /// Check if after() should run following a task with the given outcome
+    pub fn runs_after<E>(&self, outcome: &TestOutcome<E>) -> bool {
+        match self {
+            Self::Always => true,
+            Self::OnSuccess => outcome.is_passed(),
+            Self::OnFailure => !outcome.is_passed(),
+            Self::Never => false,
+        }
+    }
+}
+
+/// How the tasks of `Test::test_isolated` share the test's state
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Isolation {
+    /// Every task gets its own before() and after()
+    #[default]
+    Fresh,
+    /// Every task shares one state, built once by before() and torn down by after() once they all finish
+    Shared,
+    /// Every task shares a process-wide state, built by the first task of any run to need it and torn down by
+    /// after() once `Test::release_persistent` is called
+    Persistent,
+}
+
+/// How multi-case runners like `Test::test_each_with_config` handle a failing case
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FailureMode {
+    /// Run every case, reporting every failure
+    #[default]
+    RunAll,
+    /// Stop at the first failure, reporting every case that didn't get to run as skipped
+    FailFast,
+}
+
+/// Per-phase configuration for a single test lifecycle
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TestConfig {
+    /// Deadline for before() to produce the test's state
+    pub before_timeout: Option<Duration>,
+    /// Deadline for the test task itself
+    pub test_timeout: Option<Duration>,
+    /// Deadline for after() to finish cleaning up
+    pub after_timeout: Option<Duration>,
+    /// When after() runs, based on the task's outcome
+    pub after_policy: AfterPolicy,
+    /// Number of extra attempts, each with a fresh lifecycle, before a failing test fails
+    pub retries: usize,
+    /// How the tasks of `Test::test_isolated` share the test's state
+    pub isolation: Isolation,
+    /// Minimum number of assertions a passing task must make through spekt's own assertions
+    /// (like `ensure!`), failing tests that accidentally check nothing
+    pub min_assertions: usize,
+    /// Whether `Test::suite_with_config` runs the after() of passing tests in the background on the current
+    /// runtime, surfacing their failures once every task has run
+    pub detach_after: bool,
+    /// Whether to silence the panic hook for panics raised by the run itself, for tests that expect panics.
+    /// Panics are still reported as failures, and panics from other tests running at the same time still reach
+    /// the previous hook
+    pub suppress_panic_output: bool,
+    /// How long before() may take before a warning names the fixture and the time it took, without failing
+    /// the test, to surface setup performance regressions
+    pub slow_before_threshold: Option<Duration>,
+    /// Whether multi-case runners stop at the first failing case or run every case
+    pub failure_mode: FailureMode,
+}
+
+impl TestConfig {
+    /// Start building a config from defaults loaded from the environment: `SPEKT_TIMEOUT` for the task's deadline
+    /// (like "500ms", "5s", or "1m") and `SPEKT_RETRIES` for the number of extra attempts.
+    /// Options set explicitly on the builder take precedence. Panics if either variable is malformed
+    ///
+    /// ```
+    /// use spekt::{AfterPolicy, TestConfig};
+    /// use std::time::Duration;
+    ///
+    /// std::env::set_var("SPEKT_TIMEOUT", "5s");
+    /// std::env::set_var("SPEKT_RETRIES", "2");
+    ///
+    /// let config = TestConfig::builder()
+    ///     .retries(0)
+    ///     .after_policy(AfterPolicy::OnSuccess)
+    ///     .build();
+    ///
+    /// assert_eq!(config.test_timeout, Some(Duration::from_secs(5)));
+    /// assert_eq!(config.retries, 0);
+    /// assert_eq!(config.after_policy, AfterPolicy::OnSuccess);
+    /// ```
+    pub fn builder() -> TestConfigBuilder {
+        let mut config = Self::default();
+
+        if let Some(timeout) = env::var_os("SPEKT_TIMEOUT") {
+            let timeout = timeout.to_string_lossy();
+            let timeout = parse_duration(&timeout).unwrap_or_else(|| {
+                panic!(
+                    "invalid SPEKT_TIMEOUT {:?}, expected a duration like \"5s\"",
+                    timeout
+                )
+            });
+
+            config.test_timeout = Some(timeout);
+        }
+
+        if let Some(retries) = env::var_os("SPEKT_RETRIES") {
+            let retries = retries.to_string_lossy();
+
+            config.retries = retries.parse().unwrap_or_else(|_| {
+                panic!("invalid SPEKT_RETRIES {:?}, expected a number", retries)
+            });
+        }
+
+        TestConfigBuilder { config }
+    }
+}
+
+/// Fluent builder for a TestConfig, starting from defaults loaded from the environment
+#[derive(Clone, Debug)]
+pub struct TestConfigBuilder {
+    config: TestConfig,
+}
+
+impl TestConfigBuilder {
+    /// Set the deadline for before() to produce the test's state
+    pub fn before_timeout(mut self, timeout: Duration) -> Self {
+        self.config.before_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the deadline for the test task itself
+    pub fn test_timeout(mut self, timeout: Duration) -> Self {
+        self.config.test_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the deadline for after() to finish cleaning up
+    pub fn after_timeout(mut self, timeout: Duration) -> Self {
+        self.config.after_timeout = Some(timeout);
+        self
+    }
+
+    /// Set when after() runs, based on the task's outcome
+    pub fn after_policy(mut self, policy: AfterPolicy) -> Self {
+        self.config.after_policy = policy;
+        self
+    }
+
+    /// Set the number of extra attempts before a failing test fails
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.config.retries = retries;
+        self
+    }
+
+    /// Set how the tasks of `Test::test_isolated` share the test's state
+    pub fn isolation(mut self, isolation: Isolation) -> Self {
+        self.config.isolation = isolation;
+        self
+    }
+
+    /// Set the minimum number of assertions a passing task must make
+    pub fn min_assertions(mut self, min_assertions: usize) -> Self {
+        self.config.min_assertions = min_assertions;
+        self
+    }
+
+    /// Set whether `Test::suite_with_config` runs the after() of passing tests in the background
+    pub fn detach_after(mut self, detach_after: bool) -> Self {
+        self.config.detach_after = detach_after;
+        self
+    }
+
+    /// Set whether to silence the panic hook while the test runs
+    ///
+    /// ```
+    /// use spekt::{Test, TestConfig};
+    /// use std::{
+    ///     panic,
+    ///     sync::atomic::{AtomicUsize, Ordering},
+    /// };
+    ///
+    /// static PRINTED: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// struct Worker;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Worker {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// panic::set_hook(Box::new(|_| {
+    ///     PRINTED.fetch_add(1, Ordering::SeqCst);
+    /// }));
+    ///
+    /// let config = TestConfig::builder().suppress_panic_output(true).build();
+    ///
+    /// Worker::test_with_config(config, |_| async {
+    ///     assert!(panic::catch_unwind(|| panic!("expected")).is_err());
+    ///
+    ///     Ok(())
+    /// })
+    /// .await;
+    ///
+    /// assert_eq!(PRINTED.load(Ordering::SeqCst), 0);
+    ///
+    /// let _ = panic::catch_unwind(|| panic!("unexpected"));
+    ///
+    /// assert_eq!(PRINTED.load(Ordering::SeqCst), 1);
+    /// # }
+    /// ```
+    pub fn suppress_panic_output(mut self, suppress_panic_output: bool) -> Self {
+        self.config.suppress_panic_output = suppress_panic_output;
+        self
+    }
+
+    /// Set how long before() may take before the test warns about slow setup
+    ///
+    /// ```
+    /// use spekt::{Test, TestConfig};
+    /// use std::{
+    ///     sync::atomic::{AtomicBool, Ordering},
+    ///     time::Duration,
+    /// };
+    ///
+    /// static WARNED: AtomicBool = AtomicBool::new(false);
+    ///
+    /// struct Cluster;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Cluster {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         tokio::time::sleep(Duration::from_millis(20)).await;
+    ///
+    ///         Ok(Self)
+    ///     }
+    ///
+    ///     fn warn_slow_before(elapsed: Duration, threshold: Duration) {
+    ///         assert!(elapsed > threshold);
+    ///         WARNED.store(true, Ordering::SeqCst);
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let config = TestConfig::builder()
+    ///     .slow_before_threshold(Duration::from_millis(5))
+    ///     .build();
+    ///
+    /// Cluster::test_with_config(config, |_| async { Ok(()) }).await;
+    ///
+    /// assert!(WARNED.load(Ordering::SeqCst));
+    /// # }
+    /// ```
+    pub fn slow_before_threshold(mut self, threshold: Duration) -> Self {
+        self.config.slow_before_threshold = Some(threshold);
+        self
+    }
+
+    /// Set whether multi-case runners stop at the first failing case or run every case
+    pub fn failure_mode(mut self, failure_mode: FailureMode) -> Self {
+        self.config.failure_mode = failure_mode;
+        self
+    }
+
+    /// Finish building the config
+    pub fn build(self) -> TestConfig {
+        self.config
+    }
+}
+
+/// Parse a duration like "500ms", "5s", or "1m"
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let split = value
+        .find(|character: char| !character.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().ok()?;
+
+    match unit {
+        "ms" => Some(Duration::from_millis(amount)),
+        "s" => Some(Duration::from_secs(amount)),
+        "m" => amount.checked_mul(60).map(Duration::from_secs),
+        _ => None,
+    }
+}
@@ -0,0 +1,110 @@
+use crate::test::Test;
+use async_trait::async_trait;
+use std::fmt;
+use testcontainers::{
+    core::ContainerPort, runners::AsyncRunner, ContainerAsync, Image, TestcontainersError,
+};
+
+/// Fixture backed by a Docker container, started from the image's default configuration in before(), once the
+/// image's ready conditions are met, and stopped in after(). A container that starts but can't be reached is
+/// stopped before the setup error is returned. Tasks reach the container through its mapped ports:
+///
+/// ```no_run
+/// use spekt::{Container, Test};
+/// use testcontainers::{core::WaitFor, Image};
+///
+/// #[derive(Default)]
+/// struct Echo;
+///
+/// impl Image for Echo {
+///     fn name(&self) -> &str {
+///         "hashicorp/http-echo"
+///     }
+///
+///     fn tag(&self) -> &str {
+///         "1.0"
+///     }
+///
+///     fn ready_conditions(&self) -> Vec<WaitFor> {
+///         vec![WaitFor::message_on_stderr("server is listening")]
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// Container::<Echo>::test(|echo| async move {
+///     let url = echo.url("http", 5678).await?;
+///     assert!(url.starts_with("http://"));
+///
+///     Ok(())
+/// })
+/// .await;
+/// # }
+/// ```
+pub struct Container<I: Image> {
+    container: ContainerAsync<I>,
+    host: String,
+}
+
+impl<I: Image> Container<I> {
+    /// The running container, for anything not covered here (e.g. exec or logs)
+    pub fn inner(&self) -> &ContainerAsync<I> {
+        &self.container
+    }
+
+    /// Host that the container's mapped ports are reachable on
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Host port that a port exposed inside the container is mapped to
+    pub async fn port(&self, internal: u16) -> Result<u16, TestcontainersError> {
+        self.container
+            .get_host_port_ipv4(ContainerPort::Tcp(internal))
+            .await
+    }
+
+    /// URL with the given scheme pointing at the host port mapped to an exposed port
+    pub async fn url(&self, scheme: &str, internal: u16) -> Result<String, TestcontainersError> {
+        let port = self.port(internal).await?;
+
+        Ok(format!("{}://{}:{}", scheme, self.host, port))
+    }
+}
+
+#[async_trait]
+impl<I> Test for Container<I>
+where
+    I: Image + Default + 'static,
+{
+    type Error = TestcontainersError;
+
+    async fn before() -> Result<Self, Self::Error> {
+        let container = I::default().start().await?;
+
+        match container.get_host().await {
+            Err(error) => {
+                let _ = container.stop().await;
+                Err(error)
+            }
+            Ok(host) => Ok(Self {
+                container,
+                host: host.to_string(),
+            }),
+        }
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        self.container.stop().await
+    }
+}
+
+impl<I: Image> fmt::Debug for Container<I> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Container")
+            .field("id", &self.container.id())
+            .field("host", &self.host)
+            .finish()
+    }
+}
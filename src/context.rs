@@ -0,0 +1,433 @@
+use crate::{
+    cancel::CancelToken,
+    cleanup::CleanupStack,
+    deadline::Deadline,
+    guard::DropGuard,
+    metadata::Metadata,
+    outcome::StepReport,
+    resource::Resources,
+    test::{BoxFuture, Test},
+    unwind::catch_unwind,
+};
+use std::{
+    collections::HashSet,
+    fmt,
+    future::Future,
+    mem,
+    ops::Deref,
+    panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::Instant,
+};
+
+/// Handle to a test's state, along with utilities scoped to a single test run
+pub struct Context<S: Test> {
+    state: Arc<S>,
+    deferred: Arc<Mutex<CleanupStack<S::Error>>>,
+    cancel_token: CancelToken,
+    deadline: Option<Deadline>,
+    steps: Arc<Mutex<Vec<StepReport>>>,
+    once: Arc<Mutex<HashSet<String>>>,
+    logs: Arc<Mutex<Vec<String>>>,
+    guards: Arc<Mutex<Vec<DropGuard>>>,
+    assertions: Arc<AtomicUsize>,
+    resources: Arc<Mutex<Resources>>,
+    early_markers: Arc<Mutex<Vec<Weak<()>>>>,
+    metadata: Arc<Metadata>,
+}
+
+impl<S: Test> Context<S> {
+    pub(crate) fn new(
+        state: Arc<S>,
+        deadline: Option<Deadline>,
+        resources: Resources,
+        metadata: Metadata,
+    ) -> Self {
+        Self {
+            state,
+            deferred: Arc::new(Mutex::new(CleanupStack::new())),
+            cancel_token: CancelToken::new(),
+            deadline,
+            steps: Arc::new(Mutex::new(Vec::new())),
+            once: Arc::new(Mutex::new(HashSet::new())),
+            logs: Arc::new(Mutex::new(Vec::new())),
+            guards: Arc::new(Mutex::new(Vec::new())),
+            assertions: Arc::new(AtomicUsize::new(0)),
+            resources: Arc::new(Mutex::new(resources)),
+            early_markers: Arc::new(Mutex::new(Vec::new())),
+            metadata: Arc::new(metadata),
+        }
+    }
+
+    /// Shared handle to the test's state
+    pub fn state(&self) -> &Arc<S> {
+        &self.state
+    }
+
+    /// Token cancelled once the task finishes, for stopping background tasks it spawned
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel_token
+    }
+
+    /// Deadline of the task, if it runs with a test timeout
+    pub fn deadline(&self) -> Option<&Deadline> {
+        self.deadline.as_ref()
+    }
+
+    /// Register an async clean-up action that runs after the test task, before after().
+    /// Deferred actions run in reverse registration order, even if the task fails
+    pub fn defer<C, F>(&self, cleanup: C)
+    where
+        C: FnOnce() -> F + Send + 'static,
+        F: Future<Output = Result<(), S::Error>> + Send + 'static,
+    {
+        self.deferred.lock().unwrap().defer(cleanup);
+    }
+
+    /// Defer a clean-up action under a key, ignoring any later action registered under the same key,
+    /// so that a resource is only ever torn down once. Returns whether the action was registered
+    ///
+    /// ```
+    /// use spekt::Test;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static DROPS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// struct Database;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Database {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// Database::test_with_context(|context| async move {
+    ///     for _ in 0..2 {
+    ///         context.once("drop users", || async {
+    ///             DROPS.fetch_add(1, Ordering::SeqCst);
+    ///
+    ///             Ok(())
+    ///         });
+    ///     }
+    ///
+    ///     Ok(())
+    /// })
+    /// .await;
+    ///
+    /// assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+    /// # }
+    /// ```
+    pub fn once<C, F>(&self, key: impl Into<String>, cleanup: C) -> bool
+    where
+        C: FnOnce() -> F + Send + 'static,
+        F: Future<Output = Result<(), S::Error>> + Send + 'static,
+    {
+        let registered = self.once.lock().unwrap().insert(key.into());
+
+        if registered {
+            self.defer(cleanup);
+        }
+
+        registered
+    }
+
+    /// Run a named stage of the task, recording its duration and result in the test's report.
+    /// If the task fails after a step fails, its failure message reads `step "name" failed: ...`
+    ///
+    /// ```should_panic
+    /// use spekt::Test;
+    ///
+    /// struct Database;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Database {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let report = Database::test_reported_with_context(|context| async move {
+    ///     context.step("connect", async { Ok(()) }).await?;
+    ///     context.step("migrate", async { Err(anyhow::anyhow!("missing table")) }).await?;
+    ///     context.step("query", async { Ok(()) }).await
+    /// })
+    /// .await;
+    ///
+    /// let steps: Vec<_> = report.steps.iter().map(|step| (&*step.name, step.passed)).collect();
+    /// assert_eq!(steps, [("connect", true), ("migrate", false)]);
+    ///
+    /// // panics with `step "migrate" failed: missing table`
+    /// Database::test_with_context(|context| async move {
+    ///     context.step("migrate", async { Err(anyhow::anyhow!("missing table")) }).await
+    /// })
+    /// .await;
+    /// # }
+    /// ```
+    pub async fn step<T, F>(&self, name: &str, future: F) -> Result<T, S::Error>
+    where
+        F: Future<Output = Result<T, S::Error>>,
+    {
+        let started = Instant::now();
+        let output = future.await;
+
+        self.steps.lock().unwrap().push(StepReport {
+            name: name.to_string(),
+            duration: started.elapsed(),
+            passed: output.is_ok(),
+        });
+
+        output
+    }
+
+    /// Acquire a resource for a nested scope of the task, like a lock or a temporary table, and release it once
+    /// the scope's body finishes, even if the body fails or panics. A release error is combined with the body's
+    /// error through `Test::combine_errors`, and a panic resumes once the resource is released
+    ///
+    /// ```
+    /// use spekt::Test;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// static RELEASED: AtomicBool = AtomicBool::new(false);
+    ///
+    /// struct Database;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Database {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let outcome = Database::test_reported_with_context(|context| async move {
+    ///     context
+    ///         .with_resource(
+    ///             || async { Ok("orders_tmp".to_string()) },
+    ///             |_table| async {
+    ///                 RELEASED.store(true, Ordering::SeqCst);
+    ///
+    ///                 Ok(())
+    ///             },
+    ///             |table| Box::pin(async move { anyhow::bail!("{} is empty", table) }),
+    ///         )
+    ///         .await
+    /// })
+    /// .await
+    /// .outcome;
+    ///
+    /// assert!(!outcome.is_passed());
+    /// assert!(RELEASED.load(Ordering::SeqCst));
+    /// # }
+    /// ```
+    pub async fn with_resource<R, T, A, AF, L, LF, B>(
+        &self,
+        acquire: A,
+        release: L,
+        body: B,
+    ) -> Result<T, S::Error>
+    where
+        A: FnOnce() -> AF,
+        AF: Future<Output = Result<R, S::Error>>,
+        L: FnOnce(R) -> LF,
+        LF: Future<Output = Result<(), S::Error>>,
+        B: for<'a> FnOnce(&'a R) -> BoxFuture<'a, Result<T, S::Error>>,
+    {
+        let resource = acquire().await?;
+        let output = catch_unwind(body(&resource)).await;
+        let released = release(resource).await;
+
+        match (output, released) {
+            (Err(payload), _) => panic::resume_unwind(payload),
+            (Ok(Err(error)), Err(later)) => Err(S::combine_errors(error, later)),
+            (Ok(Err(error)), Ok(())) | (Ok(Ok(_)), Err(error)) => Err(error),
+            (Ok(Ok(output)), Ok(())) => Ok(output),
+        }
+    }
+
+    /// Logger scoped to the task, whose lines are emitted as tracing events and buffered,
+    /// then appended to the test's failure message if it fails
+    ///
+    /// ```should_panic
+    /// use spekt::Test;
+    ///
+    /// struct Database;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Database {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// // panics with `missing table\n\n---- logs ----\nmigrating to version 3`
+    /// Database::test_with_context(|context| async move {
+    ///     context.logger().log("migrating to version 3");
+    ///
+    ///     Err(anyhow::anyhow!("missing table"))
+    /// })
+    /// .await;
+    /// # }
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub fn logger(&self) -> Logger {
+        Logger {
+            lines: Arc::clone(&self.logs),
+        }
+    }
+
+    /// Number of assertions the task has made so far through spekt's own assertions, like `ensure!`
+    ///
+    /// ```
+    /// use spekt::{assert_eq_ok, ensure, Test};
+    ///
+    /// struct Fixture;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Fixture {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let report = Fixture::test_reported_with_context(|context| async move {
+    ///     ensure!(1 + 1 == 2);
+    ///     assert_eq_ok!(context.assertion_count(), 1);
+    ///
+    ///     Ok(())
+    /// })
+    /// .await;
+    ///
+    /// assert_eq!(report.assertion_count, 2);
+    /// # }
+    /// ```
+    pub fn assertion_count(&self) -> usize {
+        self.assertions.load(Ordering::SeqCst)
+    }
+
+    /// Counter of the assertions made by the task
+    pub(crate) fn assertions(&self) -> &Arc<AtomicUsize> {
+        &self.assertions
+    }
+
+    /// Registry of the resources inserted during setup and by the task
+    pub(crate) fn resources(&self) -> &Mutex<Resources> {
+        &self.resources
+    }
+
+    /// Name, attempt and parameter of the current run
+    pub(crate) fn run_metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Markers of every `CompletedEarly` handed out to the task
+    pub(crate) fn early_markers(&self) -> &Mutex<Vec<Weak<()>>> {
+        &self.early_markers
+    }
+
+    /// Hold a guard until every handle to the context is dropped
+    pub(crate) fn guard(&self, guard: DropGuard) {
+        self.guards.lock().unwrap().push(guard);
+    }
+
+    /// Take every log line buffered so far, leaving none behind
+    pub(crate) fn take_logs(&self) -> Vec<String> {
+        mem::take(&mut *self.logs.lock().unwrap())
+    }
+
+    /// Take every step recorded so far, leaving none behind
+    pub(crate) fn take_steps(&self) -> Vec<StepReport> {
+        mem::take(&mut *self.steps.lock().unwrap())
+    }
+
+    /// Take every action deferred so far, leaving none behind
+    pub(crate) fn take_deferred(&self) -> CleanupStack<S::Error> {
+        mem::take(&mut *self.deferred.lock().unwrap())
+    }
+}
+
+impl<S: Test> Clone for Context<S> {
+    fn clone(&self) -> Self {
+        Self {
+            state: Arc::clone(&self.state),
+            deferred: Arc::clone(&self.deferred),
+            cancel_token: self.cancel_token.clone(),
+            deadline: self.deadline,
+            steps: Arc::clone(&self.steps),
+            once: Arc::clone(&self.once),
+            logs: Arc::clone(&self.logs),
+            guards: Arc::clone(&self.guards),
+            assertions: Arc::clone(&self.assertions),
+            resources: Arc::clone(&self.resources),
+            early_markers: Arc::clone(&self.early_markers),
+            metadata: Arc::clone(&self.metadata),
+        }
+    }
+}
+
+impl<S: Test> Deref for Context<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.state
+    }
+}
+
+impl<S: Test + fmt::Debug> fmt::Debug for Context<S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Context")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// Logger scoped to a single test task, buffering its lines for the test's failure message
+#[cfg(feature = "tracing")]
+#[derive(Clone)]
+pub struct Logger {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+#[cfg(feature = "tracing")]
+impl Logger {
+    /// Log a line as a tracing event, keeping it for the failure message in case the test fails
+    pub fn log(&self, message: impl fmt::Display) {
+        let line = message.to_string();
+
+        tracing::info!(target: "spekt", "{}", line);
+        self.lines.lock().unwrap().push(line);
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl fmt::Debug for Logger {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Logger")
+            .field("lines", &self.lines.lock().unwrap().len())
+            .finish()
+    }
+}
@@ -0,0 +1,76 @@
+//! Bridge for benchmarking a fixture's operations with [`criterion`](https://docs.rs/criterion)
+
+use crate::{executor::block_on, test::Test};
+use ::criterion::{BatchSize, Criterion};
+use std::{future::Future, sync::Arc};
+
+/// State of a finished iteration, torn down with after() once criterion drops it outside of the measurement
+struct Teardown<S: Test>(Arc<S>);
+
+impl<S: Test> Drop for Teardown<S> {
+    fn drop(&mut self) {
+        match block_on(self.0.after()) {
+            Err(error) => panic!("{}", error),
+            Ok(Err(error)) => panic!("{}", S::fmt_failure(&error)),
+            Ok(Ok(())) => (),
+        }
+    }
+}
+
+/// Benchmark a fixture's operation with criterion's `iter_batched`, building a fresh state with before() as each
+/// batch's setup and tearing it down with after() outside of the measurement, so only the task itself is timed.
+/// Futures are driven on the current thread, so fixtures can't depend on an async runtime's reactor
+///
+/// ```no_run
+/// use criterion::Criterion;
+/// use spekt::Test;
+///
+/// struct Cache {
+///     entries: Vec<u64>,
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Test for Cache {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self {
+///             entries: (0..1024).collect(),
+///         })
+///     }
+/// }
+///
+/// let mut criterion = Criterion::default();
+///
+/// spekt::criterion::bench_fixture::<Cache, _, _>(&mut criterion, "sum", |cache| async move {
+///     assert_eq!(cache.entries.iter().sum::<u64>(), 523_776);
+///
+///     Ok(())
+/// });
+/// ```
+pub fn bench_fixture<S, F, T>(criterion: &mut Criterion, name: &str, task: T)
+where
+    S: Test,
+    F: Future<Output = Result<(), S::Error>>,
+    T: Fn(Arc<S>) -> F,
+{
+    criterion.bench_function(name, |bencher| {
+        bencher.iter_batched(
+            || match block_on(S::before()) {
+                Err(error) => panic!("{}", error),
+                Ok(Err(error)) => panic!("{}", S::fmt_failure(&error)),
+                Ok(Ok(state)) => Arc::new(state),
+            },
+            |state| {
+                match block_on(task(Arc::clone(&state))) {
+                    Err(error) => panic!("{}", error),
+                    Ok(Err(error)) => panic!("{}", S::fmt_failure(&error)),
+                    Ok(Ok(())) => (),
+                }
+
+                Teardown(state)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
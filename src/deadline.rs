@@ -0,0 +1,31 @@
+use std::time::{Duration, Instant};
+
+/// Point in time by which a test task must finish, for bounding nested operations by the time left
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Deadline {
+    instant: Instant,
+}
+
+impl Deadline {
+    /// Create a deadline expiring after the duration, starting now
+    pub fn after(duration: Duration) -> Self {
+        Self {
+            instant: Instant::now() + duration,
+        }
+    }
+
+    /// Instant at which the deadline expires
+    pub fn instant(&self) -> Instant {
+        self.instant
+    }
+
+    /// Time left before the deadline expires, or zero once it has
+    pub fn remaining(&self) -> Duration {
+        self.instant.saturating_duration_since(Instant::now())
+    }
+
+    /// Check if the deadline has already expired
+    pub fn is_expired(&self) -> bool {
+        self.remaining() == Duration::ZERO
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    cleanup::CleanupStack,
+    lifecycle::{self, Failure},
+    spawn::{spawn, Spawned, Spawner},
+    test::{BoxFuture, Test},
+};
+use std::{
+    mem,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Teardown of a passing test running in the background
+type After<E> = Spawned<Result<(), Failure<E>>>;
+
+/// Runner for the after() of passing tests in the background, holding their failures until the suite ends
+pub(crate) trait Background<S: Test>: Sync {
+    /// Start tearing down the state of a passing test in the background, followed by the clean-up registered
+    /// while setting it up
+    fn detach(&self, state: Arc<S>, cleanup: CleanupStack<S::Error>);
+
+    /// Wait for every detached teardown to finish, describing each one that failed
+    fn join(&self) -> BoxFuture<'_, Vec<String>>;
+}
+
+/// Teardowns spawned onto a runtime by `Test::suite_with_config`
+pub(crate) struct Detached<S: Test> {
+    spawner: &'static dyn Spawner,
+    after_timeout: Option<Duration>,
+    afters: Mutex<Vec<After<S::Error>>>,
+}
+
+impl<S: Test> Detached<S> {
+    pub(crate) fn new(spawner: &'static dyn Spawner, after_timeout: Option<Duration>) -> Self {
+        Self {
+            spawner,
+            after_timeout,
+            afters: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<S> Background<S> for Detached<S>
+where
+    S: Test + 'static,
+    S::Error: 'static,
+{
+    fn detach(&self, state: Arc<S>, cleanup: CleanupStack<S::Error>) {
+        let after = spawn(
+            self.spawner,
+            lifecycle::detached_after(self.after_timeout, state, cleanup),
+        );
+
+        self.afters.lock().unwrap().push(after);
+    }
+
+    fn join(&self) -> BoxFuture<'_, Vec<String>> {
+        let afters = mem::take(&mut *self.afters.lock().unwrap());
+
+        Box::pin(async move {
+            let mut failures = Vec::new();
+
+            for after in afters {
+                match after.await {
+                    Err(payload) => failures.push(lifecycle::panic_failure::<S>(&*payload)),
+                    Ok(Err(failure)) => failures.push(failure.message::<S>()),
+                    Ok(Ok(())) => (),
+                }
+            }
+
+            failures
+        })
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{context::Context, test::Test};
+use std::{error::Error, fmt, sync::Arc};
+
+/// Sentinel error returned by `Context::complete_early`, which the runner recognizes as a passing task
+/// rather than a failure for as long as the task's error still holds it, even when wrapped in another error
+#[derive(Clone)]
+pub struct CompletedEarly {
+    /// Kept alive by the task's error, which the runner checks through the context's weak handle to it
+    _marker: Arc<()>,
+}
+
+impl fmt::Debug for CompletedEarly {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("CompletedEarly")
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for CompletedEarly {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "task completed early without producing an output"
+        )
+    }
+}
+
+impl Error for CompletedEarly {}
+
+impl<S: Test> Context<S> {
+    /// End the task early as a pass, skipping the rest of its body with `?` and moving straight to teardown.
+    /// Unlike a skip, the task did run. Only an error holding the returned `CompletedEarly` counts as a pass, so it
+    /// may be wrapped (like by `anyhow::Context` or a variant of an error enum) but not discarded: a task that
+    /// returns some other error afterwards still fails with it. Tasks that produce an output other than `()` fail
+    /// with `CompletedEarly`
+    ///
+    /// ```
+    /// use spekt::Test;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// static REST_RAN: AtomicBool = AtomicBool::new(false);
+    /// static AFTER_RAN: AtomicBool = AtomicBool::new(false);
+    ///
+    /// struct Dataset {
+    ///     rows: usize,
+    /// }
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Dataset {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self { rows: 0 })
+    ///     }
+    ///
+    ///     async fn after(&self) -> Result<(), Self::Error> {
+    ///         AFTER_RAN.store(true, Ordering::SeqCst);
+    ///
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let report = Dataset::test_reported_with_context(|context| async move {
+    ///     if context.rows == 0 {
+    ///         context.complete_early()?;
+    ///     }
+    ///
+    ///     REST_RAN.store(true, Ordering::SeqCst);
+    ///     anyhow::bail!("expected an empty dataset")
+    /// })
+    /// .await;
+    ///
+    /// assert!(report.outcome.is_passed());
+    /// assert!(!REST_RAN.load(Ordering::SeqCst));
+    /// assert!(AFTER_RAN.load(Ordering::SeqCst));
+    /// # }
+    /// ```
+    pub fn complete_early<T>(&self) -> Result<T, S::Error>
+    where
+        S::Error: From<CompletedEarly>,
+    {
+        let marker = Arc::new(());
+        self.early_markers()
+            .lock()
+            .unwrap()
+            .push(Arc::downgrade(&marker));
+
+        Err(CompletedEarly { _marker: marker }.into())
+    }
+
+    /// Check if a `CompletedEarly` returned by `complete_early` is still held, which after the task finished
+    /// can only be by the error it returned
+    pub(crate) fn completed_early(&self) -> bool {
+        self.early_markers()
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|marker| marker.strong_count() > 0)
+    }
+}
@@ -0,0 +1,175 @@
+use std::{error::Error, fmt::Write};
+
+/// Format an error along with every error in its `source()` chain.
+/// Useful as a `Test::fmt_failure` override when `Test::Error` implements `std::error::Error`
+///
+/// ```
+/// use spekt::Test;
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = Box<dyn std::error::Error + Send + Sync>;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+///
+///     fn fmt_failure(error: &Self::Error) -> String {
+///         spekt::fmt_error_chain(&**error)
+///     }
+/// }
+/// ```
+pub fn fmt_error_chain<E>(error: &E) -> String
+where
+    E: Error + ?Sized,
+{
+    let mut message = error.to_string();
+    let mut source = error.source();
+
+    if source.is_some() {
+        message.push_str("\n\nCaused by:");
+    }
+
+    while let Some(cause) = source {
+        let _ = write!(message, "\n    {}", cause);
+        source = cause.source();
+    }
+
+    message
+}
+
+/// Convert a result's error into a test's unified error type through `From`, so that phases like `before`
+/// can fail with their own error types without `.map_err(From::from)` boilerplate
+///
+/// ```
+/// use spekt::{ErrInto, Test};
+/// use std::fmt;
+///
+/// #[derive(Debug)]
+/// struct ConnectError;
+///
+/// #[derive(Debug)]
+/// struct QueryError;
+///
+/// #[derive(Debug)]
+/// enum TestError {
+///     Connect(ConnectError),
+///     Query(QueryError),
+/// }
+///
+/// impl From<ConnectError> for TestError {
+///     fn from(error: ConnectError) -> Self {
+///         Self::Connect(error)
+///     }
+/// }
+///
+/// impl From<QueryError> for TestError {
+///     fn from(error: QueryError) -> Self {
+///         Self::Query(error)
+///     }
+/// }
+///
+/// impl fmt::Display for TestError {
+///     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         match self {
+///             Self::Connect(_) => write!(formatter, "failed to connect"),
+///             Self::Query(_) => write!(formatter, "failed to query"),
+///         }
+///     }
+/// }
+///
+/// struct Client;
+///
+/// impl Client {
+///     async fn connect() -> Result<Self, ConnectError> {
+///         Ok(Self)
+///     }
+///
+///     async fn query(&self) -> Result<(), QueryError> {
+///         Ok(())
+///     }
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Test for Client {
+///     type Error = TestError;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Client::connect().await.err_into()
+///     }
+/// }
+///
+/// Client::test_blocking(|client| async move { client.query().await.err_into() });
+/// ```
+pub trait ErrInto<T, E> {
+    /// Convert the error, if any, into another error type
+    fn err_into<F: From<E>>(self) -> Result<T, F>;
+}
+
+impl<T, E> ErrInto<T, E> for Result<T, E> {
+    fn err_into<F: From<E>>(self) -> Result<T, F> {
+        self.map_err(F::from)
+    }
+}
+
+/// Result returned by a test body, defaulting to the `()` output most tasks produce.
+/// Common errors like `std::io::Error` convert into `anyhow::Error` (and any other error with a matching `From`)
+/// through `?`, so bodies rarely need `.map_err`
+///
+/// ```
+/// use spekt::{Test, TestResult};
+/// use std::io::{self, Read};
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// fn read_config() -> io::Result<String> {
+///     let mut config = String::new();
+///
+///     io::Cursor::new("debug = true").read_to_string(&mut config)?;
+///
+///     Ok(config)
+/// }
+///
+/// Fixture::test_blocking(|_| async move {
+///     let config = read_config()?;
+///     anyhow::ensure!(config.contains("debug"), "missing debug flag");
+///
+///     TestResult::<(), anyhow::Error>::Ok(())
+/// });
+/// ```
+pub type TestResult<T = (), E = Box<dyn Error + Send + Sync>> = Result<T, E>;
+
+/// Convert any error into a test's unified error type through `From`, for the places `?` can't reach,
+/// like errors handed to a callback or collected from a join
+///
+/// ```
+/// use spekt::IntoTestError;
+///
+/// let error: anyhow::Error = std::io::Error::other("connection refused").into_test_error();
+///
+/// assert_eq!(error.to_string(), "connection refused");
+/// ```
+pub trait IntoTestError<E> {
+    /// Convert the error into the test's error type
+    fn into_test_error(self) -> E;
+}
+
+impl<F, E> IntoTestError<E> for F
+where
+    E: From<F>,
+{
+    fn into_test_error(self) -> E {
+        E::from(self)
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    cell::Cell,
+    error::Error,
+    fmt,
+    future::Future,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+thread_local! {
+    static ENTERED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Error returned when a blocking test is started from inside another executor that blocking would stall
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EnterError;
+
+impl fmt::Display for EnterError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "cannot block on a test from inside a running executor, await the test instead"
+        )
+    }
+}
+
+impl Error for EnterError {}
+
+/// Guard marking the current thread as running a blocking executor
+struct Enter;
+
+impl Enter {
+    /// Mark the current thread, failing if it's already driving a spekt executor or, with the `tokio` feature,
+    /// a tokio runtime whose reactor blocking would stall. Other runtimes (and tokio without the feature) keep no
+    /// state spekt can see, so blocking inside them goes undetected
+    fn new() -> Result<Self, EnterError> {
+        #[cfg(feature = "tokio")]
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(EnterError);
+        }
+
+        ENTERED.with(|entered| {
+            if entered.replace(true) {
+                Err(EnterError)
+            } else {
+                Ok(Self)
+            }
+        })
+    }
+}
+
+impl Drop for Enter {
+    fn drop(&mut self) {
+        ENTERED.with(|entered| entered.set(false));
+    }
+}
+
+/// Waker that unparks the thread driving the future
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Drive a future to completion on the current thread
+pub(crate) fn block_on<F: Future>(future: F) -> Result<F::Output, EnterError> {
+    let _enter = Enter::new()?;
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return Ok(output);
+        }
+
+        thread::park();
+    }
+}
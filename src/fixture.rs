@@ -0,0 +1,66 @@
+use crate::test::Test;
+use async_trait::async_trait;
+use std::fmt;
+
+/// Resource with a lifecycle of its own, like a database client, reusable as the state of any test.
+/// Every fixture is also a `Test`, acquired in `before` and released in `after`
+///
+/// ```
+/// use spekt::{Fixture, Test};
+///
+/// struct Client {
+///     url: String,
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Fixture for Client {
+///     type Error = anyhow::Error;
+///
+///     async fn acquire() -> Result<Self, Self::Error> {
+///         Ok(Self {
+///             url: "postgres://localhost/test".into(),
+///         })
+///     }
+/// }
+///
+/// Client::test_blocking(|client| async move {
+///     assert!(client.url.starts_with("postgres://"));
+///
+///     Ok(())
+/// });
+///
+/// Client::test_blocking(|client| async move {
+///     assert!(client.url.ends_with("/test"));
+///
+///     Ok(())
+/// });
+/// ```
+#[async_trait]
+pub trait Fixture
+where
+    Self: Sized + Send + Sync,
+{
+    /// The format-able error shared by each step
+    type Error: fmt::Display + Send + Sync;
+
+    /// Acquire a new instance of the resource
+    async fn acquire() -> Result<Self, Self::Error>;
+
+    /// Optionally release the resource once a test is done with it
+    async fn release(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<X: Fixture> Test for X {
+    type Error = X::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        X::acquire().await
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        self.release().await
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{context::Context, test::Test};
+use std::{error::Error, fmt, sync::Arc};
+
+/// Error returned by the default `Test::fork` for states that can't be forked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForkUnsupported;
+
+impl fmt::Display for ForkUnsupported {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "fork unsupported")
+    }
+}
+
+impl Error for ForkUnsupported {}
+
+impl<S: Test + 'static> Context<S> {
+    /// Create an isolated child of the test's state through `Test::fork`, for running a sub-scenario without
+    /// disturbing the parent. The child's own after() is deferred, so it's torn down before the parent
+    ///
+    /// ```
+    /// use spekt::{ForkUnsupported, Test};
+    /// use std::sync::{
+    ///     atomic::{AtomicUsize, Ordering},
+    ///     Mutex,
+    /// };
+    ///
+    /// static TORN_DOWN: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// struct Schema {
+    ///     tables: Mutex<Vec<String>>,
+    /// }
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Schema {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self {
+    ///             tables: Mutex::new(vec!["users".to_string()]),
+    ///         })
+    ///     }
+    ///
+    ///     async fn fork(&self) -> Result<Self, Self::Error>
+    ///     where
+    ///         Self::Error: From<ForkUnsupported>,
+    ///     {
+    ///         let tables = self.tables.lock().unwrap().clone();
+    ///
+    ///         Ok(Self {
+    ///             tables: Mutex::new(tables),
+    ///         })
+    ///     }
+    ///
+    ///     async fn after(&self) -> Result<(), Self::Error> {
+    ///         TORN_DOWN.fetch_add(1, Ordering::SeqCst);
+    ///
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// Schema::test_with_context(|context| async move {
+    ///     let child = context.fork().await?;
+    ///     child.tables.lock().unwrap().push("orders".to_string());
+    ///
+    ///     assert_eq!(child.tables.lock().unwrap().len(), 2);
+    ///     assert_eq!(*context.tables.lock().unwrap(), ["users"]);
+    ///
+    ///     Ok(())
+    /// })
+    /// .await;
+    ///
+    /// assert_eq!(TORN_DOWN.load(Ordering::SeqCst), 2);
+    /// # }
+    /// ```
+    pub async fn fork(&self) -> Result<Arc<S>, S::Error>
+    where
+        S::Error: From<ForkUnsupported>,
+    {
+        let child = Arc::new(self.state().fork().await?);
+        let deferred = Arc::clone(&child);
+
+        self.defer(move || async move { deferred.after().await });
+
+        Ok(child)
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{outcome::TestOutcome, test::BoxFuture};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Whole lifecycle of a test as a plain future resolving to its outcome, for embedding spekt in custom executors
+/// that need full control over polling and wakers, e.g. to advance virtual time between polls
+///
+/// ```
+/// use spekt::Test;
+/// use std::{
+///     future::Future,
+///     pin::pin,
+///     task::{Context, Poll, Waker},
+///     time::Duration,
+/// };
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread()
+///     .enable_time()
+///     .start_paused(true)
+///     .build()
+///     .unwrap();
+///
+/// runtime.block_on(async {
+///     let mut lifecycle = pin!(Fixture::test_future(|_| async {
+///         tokio::time::sleep(Duration::from_secs(60)).await;
+///
+///         Ok(())
+///     }));
+///     let mut context = Context::from_waker(Waker::noop());
+///
+///     assert!(lifecycle.as_mut().poll(&mut context).is_pending());
+///
+///     tokio::time::advance(Duration::from_secs(60)).await;
+///
+///     match lifecycle.as_mut().poll(&mut context) {
+///         Poll::Ready(outcome) => assert!(outcome.is_passed()),
+///         Poll::Pending => panic!("task still waiting after a minute of virtual time"),
+///     }
+/// });
+/// ```
+pub struct TestFuture<'a, E> {
+    run: BoxFuture<'a, TestOutcome<E>>,
+}
+
+impl<'a, E> TestFuture<'a, E> {
+    pub(crate) fn new(run: BoxFuture<'a, TestOutcome<E>>) -> Self {
+        Self { run }
+    }
+}
+
+impl<E> Future for TestFuture<'_, E> {
+    type Output = TestOutcome<E>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        self.run.as_mut().poll(context)
+    }
+}
+
+impl<E> fmt::Debug for TestFuture<'_, E> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_struct("TestFuture").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{context::Context, test::Test};
+use std::{
+    fmt,
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::Mutex,
+    task::{self, Poll, Waker},
+};
+
+/// Available permits and the wakers of every task waiting for one
+struct Permits {
+    available: usize,
+    waiting: Vec<Waker>,
+}
+
+/// Async semaphore bounding how many tests hold a scarce resource at once, like a fixed pool of databases.
+/// Gates can be declared as statics and shared across every test in a binary
+///
+/// ```
+/// use spekt::{Gate, Test};
+/// use std::{
+///     sync::atomic::{AtomicUsize, Ordering},
+///     time::Duration,
+/// };
+///
+/// static DATABASES: Gate = Gate::new(1);
+/// static HOLDERS: AtomicUsize = AtomicUsize::new(0);
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// async fn hold_database() {
+///     Fixture::test_with_context(|context| async move {
+///         context.acquire(&DATABASES).await;
+///
+///         assert_eq!(HOLDERS.fetch_add(1, Ordering::SeqCst), 0);
+///         tokio::time::sleep(Duration::from_millis(10)).await;
+///         HOLDERS.fetch_sub(1, Ordering::SeqCst);
+///
+///         Ok(())
+///     })
+///     .await
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// tokio::join!(hold_database(), hold_database());
+/// # }
+/// ```
+pub struct Gate {
+    permits: Mutex<Permits>,
+}
+
+impl Gate {
+    /// Create a gate that lets through a fixed number of holders at once
+    pub const fn new(permits: usize) -> Self {
+        Self {
+            permits: Mutex::new(Permits {
+                available: permits,
+                waiting: Vec::new(),
+            }),
+        }
+    }
+
+    /// Wait for a permit, held until the returned guard is dropped
+    pub fn acquire(&self) -> Acquire<'_> {
+        Acquire { gate: self }
+    }
+
+    /// Number of permits that are currently free
+    pub fn available(&self) -> usize {
+        self.permits.lock().unwrap().available
+    }
+}
+
+impl fmt::Debug for Gate {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Gate")
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+/// Future that resolves to a permit once the gate has one free
+#[derive(Debug)]
+pub struct Acquire<'a> {
+    gate: &'a Gate,
+}
+
+impl<'a> Future for Acquire<'a> {
+    type Output = Permit<'a>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut permits = self.gate.permits.lock().unwrap();
+
+        if permits.available > 0 {
+            permits.available -= 1;
+
+            return Poll::Ready(Permit { gate: self.gate });
+        }
+
+        if !permits
+            .waiting
+            .iter()
+            .any(|waker| waker.will_wake(context.waker()))
+        {
+            permits.waiting.push(context.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Guard holding one of a gate's permits, released when dropped
+#[derive(Debug)]
+pub struct Permit<'a> {
+    gate: &'a Gate,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let waiting = {
+            let mut permits = self.gate.permits.lock().unwrap();
+
+            permits.available += 1;
+            mem::take(&mut permits.waiting)
+        };
+
+        // every waiter is woken to race for the permit, so that waiters that gave up can't swallow it
+        for waker in waiting {
+            waker.wake();
+        }
+    }
+}
+
+impl<S: Test> Context<S> {
+    /// Wait for one of the gate's permits, holding it until the task's deferred clean-up runs, even if the task
+    /// fails or panics
+    pub async fn acquire(&self, gate: &'static Gate) {
+        let permit = gate.acquire().await;
+
+        self.defer(move || async move {
+            drop(permit);
+
+            Ok(())
+        });
+    }
+}
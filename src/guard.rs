@@ -0,0 +1,67 @@
+use crate::{context::Context, test::Test};
+use std::fmt;
+
+/// Best-effort, synchronous last-resort clean-up that runs when the guard is dropped, for fixtures whose async
+/// after() might never run (e.g. because the test's future was cancelled or its thread unwound).
+/// Nothing runs if the process is killed outright, so this complements after() rather than replacing it
+///
+/// ```
+/// use spekt::DropGuard;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// static DELETED: AtomicBool = AtomicBool::new(false);
+///
+/// struct Fixture {
+///     _guard: DropGuard,
+/// }
+///
+/// let fixture = Fixture {
+///     _guard: DropGuard::new(|| DELETED.store(true, Ordering::SeqCst)),
+/// };
+///
+/// std::mem::drop(fixture);
+///
+/// assert!(DELETED.load(Ordering::SeqCst));
+/// ```
+pub struct DropGuard {
+    cleanup: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl DropGuard {
+    /// Create a guard that runs the clean-up when dropped
+    pub fn new(cleanup: impl FnOnce() + Send + Sync + 'static) -> Self {
+        Self {
+            cleanup: Some(Box::new(cleanup)),
+        }
+    }
+
+    /// Drop the guard without running its clean-up, e.g. once after() has cleaned up
+    pub fn disarm(mut self) {
+        self.cleanup = None;
+    }
+}
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        if let Some(cleanup) = self.cleanup.take() {
+            cleanup();
+        }
+    }
+}
+
+impl fmt::Debug for DropGuard {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("DropGuard")
+            .field("armed", &self.cleanup.is_some())
+            .finish()
+    }
+}
+
+impl<S: Test> Context<S> {
+    /// Register a synchronous, best-effort fallback that runs once the test's lifecycle is over,
+    /// including when its future is dropped before after() gets to run
+    pub fn on_drop(&self, cleanup: impl FnOnce() + Send + Sync + 'static) {
+        self.guard(DropGuard::new(cleanup));
+    }
+}
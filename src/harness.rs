@@ -0,0 +1,116 @@
+//! Standalone harness for `harness = false` test targets, with libtest's command-line filtering and parallelism
+
+use crate::{executor::block_on, outcome::TestOutcome, test::Test};
+use libtest_mimic::{Completion, Failed, Trial};
+use std::{future::Future, sync::Arc};
+
+pub use libtest_mimic::{Arguments, Conclusion};
+
+/// Collection of named test tasks, each run through its fixture's lifecycle on libtest-mimic's worker threads.
+/// Tasks are driven by spekt's runtime-agnostic blocking executor, so they can't rely on a runtime's reactor
+///
+/// ```
+/// use spekt::{harness::{Arguments, Harness}, Test};
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// let mut harness = Harness::new();
+///
+/// harness
+///     .register::<Fixture, _, _>("passes", |_| async { Ok(()) })
+///     .register::<Fixture, _, _>("fails", |_| async { anyhow::bail!("expected failure") });
+///
+/// let conclusion = harness.run(&Arguments {
+///     test_threads: Some(1),
+///     quiet: true,
+///     ..Arguments::default()
+/// });
+///
+/// assert_eq!(conclusion.num_passed, 1);
+/// assert_eq!(conclusion.num_failed, 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct Harness {
+    trials: Vec<Trial>,
+}
+
+impl Harness {
+    /// Create a harness without any tests
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a named test task against a fixture type, observed by its `on_start` and `on_finish` hooks.
+    /// Skipped and flaky outcomes are reported as ignored
+    pub fn register<S, F, T>(&mut self, name: impl Into<String>, task: T) -> &mut Self
+    where
+        S: Test + 'static,
+        F: Future<Output = Result<(), S::Error>> + Send,
+        T: Send + Sync + 'static + FnOnce(Arc<S>) -> F,
+    {
+        let name = name.into();
+        let trial = Trial::ignorable_test(name.clone(), move || {
+            let outcome = block_on(async {
+                S::on_start(&name).await;
+
+                let outcome = S::run(task).await;
+
+                S::on_finish(&name, &outcome).await;
+                outcome
+            })?;
+
+            completion(outcome.map_failure(|error| S::fmt_failure(&error)))
+        });
+
+        self.trials.push(trial);
+        self
+    }
+
+    /// Register every test collected with `#[spekt::register]`, named by their full paths
+    #[cfg(feature = "inventory")]
+    pub fn register_collected(&mut self) -> &mut Self {
+        for test in crate::collect::collected_tests() {
+            let trial =
+                Trial::ignorable_test(test.name(), move || completion(block_on(test.run())?));
+
+            self.trials.push(trial);
+        }
+
+        self
+    }
+
+    /// Run every registered test matching the arguments
+    pub fn run(self, arguments: &Arguments) -> Conclusion {
+        libtest_mimic::run(arguments, self.trials)
+    }
+
+    /// Run every registered test matching the process's command-line arguments, then exit with libtest's status
+    pub fn run_main(self) -> ! {
+        self.run(&Arguments::from_args()).exit()
+    }
+}
+
+/// Map the outcome of a test to libtest's, reporting skipped and flaky tests as ignored
+fn completion(outcome: TestOutcome<String>) -> Result<Completion, Failed> {
+    match outcome {
+        TestOutcome::Passed => Ok(Completion::Completed),
+        TestOutcome::Skipped => Ok(Completion::ignored()),
+        TestOutcome::Flaky(errors) => Ok(Completion::Ignored {
+            reason: Some(format!("flaky: {}", errors.join("; "))),
+        }),
+        TestOutcome::Failed(message) => Err(Failed::from(message)),
+        TestOutcome::TimedOut(duration) => {
+            Err(Failed::from(format!("timed out after {:?}", duration)))
+        }
+        TestOutcome::Panicked(message) => Err(Failed::from(format!("panicked: {}", message))),
+    }
+}
@@ -0,0 +1,73 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    panic,
+    pin::Pin,
+    sync::Once,
+    task::{Context, Poll},
+};
+
+thread_local! {
+    static SILENCED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Guards installing the silencing hook only once per process
+static INSTALL: Once = Once::new();
+
+/// Wrap the process's panic hook once, skipping it for panics raised while a silenced future is being polled on
+/// the current thread and forwarding every other panic to the previous hook
+fn install() {
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if SILENCED.with(Cell::get) == 0 {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Guard restoring the silenced depth of the current thread once a future is done being polled
+struct Restore(usize);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        SILENCED.with(|silenced| silenced.set(self.0));
+    }
+}
+
+/// Future that silences the panic hook for panics raised while it's being polled. Panics are still caught and
+/// reported by the lifecycle, just not printed by the hook. Panics on other threads (e.g. in tasks spawned onto
+/// a multi-threaded runtime) and in concurrently running tests still reach the previous hook
+pub(crate) struct Silenced<F> {
+    future: Pin<Box<F>>,
+    enabled: bool,
+}
+
+impl<F: Future> Future for Silenced<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        if !self.enabled {
+            return self.future.as_mut().poll(context);
+        }
+
+        let outer = SILENCED.with(|silenced| silenced.replace(silenced.get() + 1));
+        let _restore = Restore(outer);
+
+        self.future.as_mut().poll(context)
+    }
+}
+
+/// Silence the panic hook while polling a future, if enabled
+pub(crate) fn silenced<F: Future>(enabled: bool, future: F) -> Silenced<F> {
+    if enabled {
+        install();
+    }
+
+    Silenced {
+        future: Box::pin(future),
+        enabled,
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Future that drives two fallible futures concurrently, resolving once both succeed or as soon as either fails
+pub(crate) struct TryJoin<A: Future, B: Future> {
+    first: Pin<Box<A>>,
+    second: Pin<Box<B>>,
+    outputs: (Option<A::Output>, Option<B::Output>),
+}
+
+// both futures are boxed, so their outputs are never pinned
+impl<A: Future, B: Future> Unpin for TryJoin<A, B> {}
+
+impl<A, B, T, U, E> Future for TryJoin<A, B>
+where
+    A: Future<Output = Result<T, E>>,
+    B: Future<Output = Result<U, E>>,
+{
+    type Output = (Option<A::Output>, Option<B::Output>);
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.outputs.0.is_none() {
+            if let Poll::Ready(output) = this.first.as_mut().poll(context) {
+                this.outputs.0 = Some(output);
+            }
+        }
+
+        if this.outputs.1.is_none() && !matches!(this.outputs.0, Some(Err(_))) {
+            if let Poll::Ready(output) = this.second.as_mut().poll(context) {
+                this.outputs.1 = Some(output);
+            }
+        }
+
+        match &this.outputs {
+            (Some(Ok(_)), Some(Ok(_))) | (Some(Err(_)), _) | (_, Some(Err(_))) => {
+                Poll::Ready((this.outputs.0.take(), this.outputs.1.take()))
+            }
+            _ => Poll::Pending,
+        }
+    }
+}
+
+/// Run two fallible futures concurrently, abandoning whichever is still pending once the other fails.
+/// Resolves to the output of each future that finished, leaving `None` for one that was abandoned
+pub(crate) fn try_join<A, B, T, U, E>(first: A, second: B) -> TryJoin<A, B>
+where
+    A: Future<Output = Result<T, E>>,
+    B: Future<Output = Result<U, E>>,
+{
+    TryJoin {
+        first: Box::pin(first),
+        second: Box::pin(second),
+        outputs: (None, None),
+    }
+}
+
+/// Future that drives a bounded number of futures concurrently, resolving to their outputs in order
+pub(crate) struct JoinBounded<F: Future> {
+    queued: VecDeque<(usize, F)>,
+    running: Vec<(usize, Pin<Box<F>>)>,
+    outputs: Vec<Option<F::Output>>,
+    limit: usize,
+}
+
+// queued futures are only pinned once they are boxed, so the queue itself is never pinned
+impl<F: Future> Unpin for JoinBounded<F> {}
+
+impl<F: Future> Future for JoinBounded<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            while this.running.len() < this.limit {
+                match this.queued.pop_front() {
+                    Some((index, future)) => this.running.push((index, Box::pin(future))),
+                    None => break,
+                }
+            }
+
+            let before = this.running.len();
+            let outputs = &mut this.outputs;
+
+            this.running
+                .retain_mut(|(index, future)| match future.as_mut().poll(context) {
+                    Poll::Ready(output) => {
+                        outputs[*index] = Some(output);
+                        false
+                    }
+                    Poll::Pending => true,
+                });
+
+            if this.running.is_empty() && this.queued.is_empty() {
+                let outputs = this.outputs.drain(..).map(Option::unwrap).collect();
+
+                return Poll::Ready(outputs);
+            }
+
+            if this.running.len() == before {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+/// Run futures concurrently, with at most `limit` of them in flight at once
+pub(crate) fn join_bounded<F: Future>(futures: Vec<F>, limit: usize) -> JoinBounded<F> {
+    let outputs = futures.iter().map(|_| None).collect();
+
+    JoinBounded {
+        queued: futures.into_iter().enumerate().collect(),
+        running: Vec::new(),
+        outputs,
+        limit: limit.max(1),
+    }
+}
@@ -7,7 +7,7 @@ Most Rust unit tests terminate based on `panic`s (generally triggered by `assert
 with resource clean-up implemented manually through the `Drop` trait. Working synchronously with a stateful resource
 like a database might look like this:
 
-```
+```ignore
 use postgres::{Client, NoTls, Row, error::Error as PostgresError};
 
 struct PostgresTest {
@@ -48,16 +48,16 @@ fn adds_queryable_test_table() {
 While this works for many cases, there are a couple of issues with this recommendation:
 
 1. Technically, Rust doesn't _guarantee_ that `Drop` will be run,
-and [one shouldn't rely on `Drop` to be run in all cases](http://cglab.ca/%7Eabeinges/blah/everyone-poops/).
+   and [one shouldn't rely on `Drop` to be run in all cases](http://cglab.ca/%7Eabeinges/blah/everyone-poops/).
 2. `Drop` also cannot be asynchronous!
-There has been much discussion around [Asynchronous destructors](https://internals.rust-lang.org/t/asynchronous-destructors/11127),
-but no reliable destructor trait has yet materialized for `async` functions.
+   There has been much discussion around [Asynchronous destructors](https://internals.rust-lang.org/t/asynchronous-destructors/11127),
+   but no reliable destructor trait has yet materialized for `async` functions.
 3. `panic`-based assertions (and their associated unwinding) also behave in ways that
-[might be unpredictable across runtimes](https://github.com/tokio-rs/tokio/issues/2002).
-This is, specifically, an [issue in tests](https://github.com/tokio-rs/tokio/issues/2699) for which there is no good universal solution.
+   [might be unpredictable across runtimes](https://github.com/tokio-rs/tokio/issues/2002).
+   This is, specifically, an [issue in tests](https://github.com/tokio-rs/tokio/issues/2699) for which there is no good universal solution.
 4. In addition, while `new` and `Drop` make sense for resources, those conventions make less sense for the more abstract idea of a "Test".
-In most testing frameworks, the idea of a "test" is the combination of some stateful test context initialized `before` the actual test,
-a test case that can mutate its own context, and some clean-up to be run `after` the actual test.
+   In most testing frameworks, the idea of a "test" is the combination of some stateful test context initialized `before` the actual test,
+   a test case that can mutate its own context, and some clean-up to be run `after` the actual test.
 
 `spekt` avoids all of these issues by providing a `Test` trait
 that encompasses the `before` -> `test` -> `after` lifecycle of stateful `async` tests that use `Result` to drive assertions.
@@ -65,13 +65,13 @@ that encompasses the `before` -> `test` -> `after` lifecycle of stateful `async`
 ## How to use:
 
 `spekt::Test` can be implemented for any `Send + Sync` test state, enabling a `test()` method that returns a `std::future::Future`.
-The returned `Future` is runtime-agnostic, and can be evaluated synchronously with `.wait()`, through a per-suite custom runtime
+The returned `Future` is runtime-agnostic, and can be evaluated synchronously with `Test::test_blocking`, through a per-suite custom runtime
 (e.g. [`tokio::runtime::Runtime`](https://docs.rs/tokio/0.2.22/tokio/runtime/struct.Runtime.html)),
 or through an `async` test-runner like [`tokio::test`](https://docs.rs/tokio/0.2.22/tokio/attr.test.html).
 
 Rewriting the example above with `spekt::Test`:
 
-```
+```ignore
 use tokio_postgres::{Client, NoTls, Row, error::Error as PostgresError};
 use spekt::Test;
 
@@ -111,9 +111,195 @@ async fn adds_queryable_test_table() {
     }).await
 }
 ```
+
+## Features
+
+- `macros` (default): adds a `#[spekt::test]` attribute that turns an `async fn(Arc<Fixture>) -> Result<(), Fixture::Error>`
+  into a `#[tokio::test]` driving the fixture's lifecycle, with optional `timeout = "5s"` and `retries = 3` arguments,
+  plus a `#[derive(Test)]` for `Default`-constructible fixtures with nothing to tear down, customizable with
+  `#[spekt(error = "MyError")]` and `#[spekt(before = path::to_fn)]`. Derived fixtures fail with `anyhow::Error`
+  unless they name another error, so crates relying on that default must depend on `anyhow`
+- `async-std`: adds `Test::test_blocking_async_std`, driving a test on the current thread with
+  [`async-std`](https://docs.rs/async-std)'s executor instead of the built-in one, and an `AsyncStdSpawner`
+- `capture`: adds `Test::test_captured`, which redirects stdout and stderr with [`gag`](https://docs.rs/gag) while a task
+  runs and appends the captured output to its failure message
+- `color`: paints failure messages red and formats failures of `Test::test_named` as a block with a bold header and
+  the time spent in each phase, with [`owo-colors`](https://docs.rs/owo-colors). Falls back to plain text when stdout
+  isn't a terminal or `NO_COLOR` is set
+- `criterion`: adds `spekt::criterion::bench_fixture`, benchmarking a task against a fresh state per batch with
+  [`criterion`](https://docs.rs/criterion)'s `iter_batched`, keeping before() and after() out of the measurement
+- `dotenv`: loads a `.env` file in the default `Test::env` hook, which runs before each `before`
+- `harness`: adds a `spekt::harness` module for `harness = false` test targets, registering named tasks and running
+  them through [`libtest-mimic`](https://docs.rs/libtest-mimic) with libtest's command-line filtering and parallelism
+- `inventory`: adds a `#[spekt::register]` attribute that collects tests at link time through
+  [`inventory`](https://docs.rs/inventory), discoverable with `spekt::collected_tests()` and `Harness::register_collected`
+- `json`: adds `spekt::report::Json`, which serializes named test reports as JSON lines with
+  [`serde_json`](https://docs.rs/serde_json) for log pipelines and custom dashboards
+- `local`: adds a `LocalTest` trait for `!Send` test state (e.g. `Rc`-based clients), driven on the current thread,
+  whose tasks receive the state through an `Rc` by default, or through `Arc` or a custom handle implementing `Shared`.
+  `Test` itself always shares its state through an `Arc`
+- `proptest`: adds `Test::test_prop`, running a lifecycle per input drawn from a [`proptest`](https://docs.rs/proptest)
+  strategy and shrinking failing inputs
+- `rand`: adds `Context::rng`, a [`rand`](https://docs.rs/rand) `StdRng` seeded with `Context::seed`, which is
+  derived from the test's name and attempt (or taken from `SPEKT_SEED`) so that random failures reproduce
+- `tempfile`: adds `spekt::scratch_dir()` and `Context::scratch_dir()` for unique temporary directories, the latter
+  removed along with the test's other deferred clean-up
+- `testcontainers`: adds a `Container<I>` fixture that starts a Docker container from a
+  [`testcontainers`](https://docs.rs/testcontainers) image in before(), waiting until it's ready, exposes its
+  mapped ports and URLs to the task, and stops it in after()
+- `tokio`: enforces phase timeouts like `Test::test_with_timeout` with `tokio::time::timeout` when running inside a
+  tokio runtime, so deadlines follow the runtime's clock (including `tokio::time::pause`) instead of a timer thread.
+  That runtime needs its timer enabled, which `#[tokio::test]` and `#[tokio::main]` do, or timed phases panic.
+  Either way, an expired task future is dropped, cancelling any work it owns; tasks detached with `tokio::spawn`
+  keep running unless they watch `Context::cancel_token`. Also adds a `TokioSpawner` for `Test::test_parallel_spawned`,
+  and lets `Test::test_blocking` detect (and refuse) being called from inside a tokio runtime
+- `tracing`: wraps the `before`, test, and `after` phases of each run in `spekt.before`, `spekt.test`, and `spekt.after`
+  [`tracing`](https://docs.rs/tracing) spans, recording each phase's duration in milliseconds as a `duration_ms` field,
+  and adds `Context::logger` for logs that are only shown when a test fails
 */
 #[deny(missing_docs, unreachable_pub)]
+mod assert;
+#[deny(missing_docs, unreachable_pub)]
+mod bench;
+#[deny(missing_docs, unreachable_pub)]
+mod cancel;
+#[cfg(feature = "capture")]
+#[deny(missing_docs, unreachable_pub)]
+mod capture;
+#[deny(missing_docs, unreachable_pub)]
+mod chain;
+#[deny(missing_docs, unreachable_pub)]
+mod cleanup;
+#[cfg(feature = "inventory")]
+#[deny(missing_docs, unreachable_pub)]
+mod collect;
+#[deny(missing_docs, unreachable_pub)]
+mod compose;
+#[deny(missing_docs, unreachable_pub)]
+mod config;
+#[cfg(feature = "testcontainers")]
+#[deny(missing_docs, unreachable_pub)]
+mod container;
+#[deny(missing_docs, unreachable_pub)]
+mod context;
+#[cfg(feature = "criterion")]
+#[deny(missing_docs, unreachable_pub)]
+pub mod criterion;
+#[deny(missing_docs, unreachable_pub)]
+mod deadline;
+#[deny(missing_docs, unreachable_pub)]
+mod detach;
+#[deny(missing_docs, unreachable_pub)]
+mod early;
+#[deny(missing_docs, unreachable_pub)]
+mod error;
+#[deny(missing_docs, unreachable_pub)]
+mod executor;
+#[deny(missing_docs, unreachable_pub)]
+mod fixture;
+#[deny(missing_docs, unreachable_pub)]
+mod fork;
+#[deny(missing_docs, unreachable_pub)]
+mod future;
+#[deny(missing_docs, unreachable_pub)]
+mod gate;
+#[deny(missing_docs, unreachable_pub)]
+mod guard;
+#[cfg(feature = "harness")]
+#[deny(missing_docs, unreachable_pub)]
+pub mod harness;
+#[deny(missing_docs, unreachable_pub)]
+mod hook;
+#[deny(missing_docs, unreachable_pub)]
+mod join;
+#[deny(missing_docs, unreachable_pub)]
+mod lifecycle;
+#[cfg(feature = "local")]
+#[deny(missing_docs, unreachable_pub)]
+mod local;
+#[deny(missing_docs, unreachable_pub)]
+mod metadata;
+#[deny(missing_docs, unreachable_pub)]
+mod net;
+#[deny(missing_docs, unreachable_pub)]
+mod outcome;
+#[deny(missing_docs, unreachable_pub)]
+mod params;
+#[deny(missing_docs, unreachable_pub)]
+mod pretty;
+#[deny(missing_docs, unreachable_pub)]
+pub mod report;
+#[deny(missing_docs, unreachable_pub)]
+mod resource;
+#[deny(missing_docs, unreachable_pub)]
+mod retry;
+#[deny(missing_docs, unreachable_pub)]
+mod rollback;
+#[cfg(feature = "tempfile")]
+#[deny(missing_docs, unreachable_pub)]
+mod scratch;
+#[deny(missing_docs, unreachable_pub)]
+mod seed;
+#[deny(missing_docs, unreachable_pub)]
+mod shared;
+#[deny(missing_docs, unreachable_pub)]
+mod soft;
+#[deny(missing_docs, unreachable_pub)]
+mod spawn;
+#[deny(missing_docs, unreachable_pub)]
+mod suite_report;
+#[deny(missing_docs, unreachable_pub)]
 mod test;
+#[deny(missing_docs, unreachable_pub)]
+mod timer;
+#[deny(missing_docs, unreachable_pub)]
+mod trace;
+#[deny(missing_docs, unreachable_pub)]
+mod unwind;
+#[deny(missing_docs, unreachable_pub)]
+mod workflow;
 
+pub use self::assert::*;
+pub use self::bench::*;
+pub use self::cancel::*;
+pub use self::chain::*;
+pub use self::cleanup::*;
+#[cfg(feature = "inventory")]
+pub use self::collect::*;
+pub use self::config::*;
+#[cfg(feature = "testcontainers")]
+pub use self::container::*;
+pub use self::context::*;
+pub use self::deadline::*;
+pub use self::early::*;
+pub use self::error::*;
+pub use self::executor::EnterError;
+pub use self::fixture::*;
+pub use self::fork::*;
+pub use self::future::*;
+pub use self::gate::*;
+pub use self::guard::*;
+#[cfg(feature = "local")]
+pub use self::local::*;
+pub use self::metadata::*;
+pub use self::net::*;
+pub use self::outcome::*;
+pub use self::params::*;
+pub use self::resource::*;
+pub use self::retry::*;
+pub use self::rollback::*;
+#[cfg(feature = "tempfile")]
+pub use self::scratch::*;
+pub use self::shared::*;
+pub use self::soft::*;
+pub use self::spawn::*;
+pub use self::suite_report::*;
 pub use self::test::*;
+pub use self::timer::*;
+pub use self::unwind::*;
+pub use self::workflow::*;
 pub use async_trait::async_trait;
+#[cfg(feature = "inventory")]
+pub use spekt_macros::register;
+#[cfg(feature = "macros")]
+pub use spekt_macros::{test, Test};
@@ -0,0 +1,976 @@
+use crate::{
+    assert::{counted, AssertionError},
+    cleanup::CleanupStack,
+    config::TestConfig,
+    context::Context,
+    deadline::Deadline,
+    detach::Background,
+    gate::Gate,
+    hook::silenced,
+    metadata::Metadata,
+    outcome::{BeforeOutcome, StepReport, TestOutcome, TestReport},
+    params::Parameterized,
+    resource::Resources,
+    suite_report::Collector,
+    test::{BoxFuture, Test},
+    timer::phase_timeout,
+    trace::traced,
+    unwind::{catch_unwind, panic_message},
+};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    future::Future,
+    mem, panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// Lifecycle phase of a single test
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Phase {
+    Before,
+    Test,
+    After,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Before => write!(formatter, "before()"),
+            Self::Test => write!(formatter, "test()"),
+            Self::After => write!(formatter, "after()"),
+        }
+    }
+}
+
+/// Failure of a single lifecycle phase
+#[derive(Debug)]
+pub(crate) enum Failure<E> {
+    Error(E),
+    TimedOut(Phase, Duration),
+    Skipped(String),
+    Assertions(AssertionError),
+}
+
+impl<E> Failure<E> {
+    /// Format the failure, using the test's own formatting for its errors
+    pub(crate) fn message<S>(&self) -> String
+    where
+        S: Test<Error = E>,
+    {
+        match self {
+            Self::Error(error) => S::fmt_failure(error),
+            Self::TimedOut(phase, duration) => format!("{} timed out after {:?}", phase, duration),
+            Self::Skipped(reason) => format!("skipped: {}", reason),
+            Self::Assertions(error) => error.to_string(),
+        }
+    }
+
+    /// Convert the failure into the outcome of a test
+    pub(crate) fn into_outcome(self) -> TestOutcome<E> {
+        match self {
+            Self::Error(error) => TestOutcome::Failed(error),
+            Self::TimedOut(_, duration) => TestOutcome::TimedOut(duration),
+            Self::Skipped(_) => TestOutcome::Skipped,
+            Self::Assertions(_) => {
+                unreachable!("assertion failures are converted once the task finishes")
+            }
+        }
+    }
+
+    /// Unwrap the error of a lifecycle run without any deadlines
+    pub(crate) fn into_error(self) -> E {
+        match self {
+            Self::Error(error) => error,
+            Self::TimedOut(phase, _) => unreachable!("{} ran without a deadline", phase),
+            Self::Skipped(_) => unreachable!("skipped runs have no output rather than an error"),
+            Self::Assertions(_) => {
+                unreachable!("assertion failures are converted once the task finishes")
+            }
+        }
+    }
+}
+
+/// Output of a task along with the result of tearing down its state
+pub(crate) struct Run<R, E> {
+    pub(crate) output: Result<R, Failure<E>>,
+    pub(crate) teardown: Result<(), Failure<E>>,
+    pub(crate) timings: Timings,
+    pub(crate) steps: Vec<StepReport>,
+    pub(crate) logs: Vec<String>,
+    pub(crate) assertions: usize,
+}
+
+/// Time spent in each phase of a lifecycle run
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Timings {
+    pub(crate) before: Duration,
+    pub(crate) test: Duration,
+    pub(crate) after: Duration,
+}
+
+/// Await a future, measuring how long it took to resolve
+async fn timed<F: Future>(future: F) -> (F::Output, Duration) {
+    let started = Instant::now();
+    let output = future.await;
+
+    (output, started.elapsed())
+}
+
+/// Warn if before() took longer than the config allows, without failing the test
+fn check_slow_before<S: Test>(config: &TestConfig, before: Duration) {
+    if let Some(threshold) = config
+        .slow_before_threshold
+        .filter(|threshold| before > *threshold)
+    {
+        S::warn_slow_before(before, threshold);
+    }
+}
+
+/// Await a phase, failing if it exceeds its optional deadline
+async fn within<F, O, E>(
+    phase: Phase,
+    duration: Option<Duration>,
+    future: F,
+) -> Result<O, Failure<E>>
+where
+    F: Future<Output = Result<O, E>>,
+{
+    traced(phase, async {
+        match duration {
+            None => future.await.map_err(Failure::Error),
+            Some(duration) => match phase_timeout(duration, future).await {
+                Err(_) => Err(Failure::TimedOut(phase, duration)),
+                Ok(output) => output.map_err(Failure::Error),
+            },
+        }
+    })
+    .await
+}
+
+/// State built by setup, along with the clean-up it registered and the resources it inserted
+type Setup<S> = (S, CleanupStack<<S as Test>::Error>, Resources);
+
+/// Build a test's state, running any registered clean-up if setup fails or panics
+async fn setup<S: Test>(config: &TestConfig) -> Result<Setup<S>, Failure<S::Error>> {
+    let mut cleanup = CleanupStack::new();
+    let mut resources = Resources::new();
+    let before = catch_unwind(within(Phase::Before, config.before_timeout, async {
+        S::env().await?;
+        S::before_or_skip(&mut cleanup, &mut resources).await
+    }))
+    .await;
+
+    match before {
+        Err(payload) => {
+            let _ = cleanup.run().await;
+            panic::resume_unwind(payload)
+        }
+        Ok(Err(failure)) => {
+            let _ = cleanup.run().await;
+            Err(failure)
+        }
+        Ok(Ok(BeforeOutcome::Skip(reason))) => {
+            let _ = cleanup.run().await;
+            Err(Failure::Skipped(reason))
+        }
+        Ok(Ok(BeforeOutcome::Ready(state))) => Ok((state, cleanup, resources)),
+    }
+}
+
+/// Build a test's state from runtime parameters, leaving no clean-up registered or resources inserted
+async fn setup_with<S: Parameterized>(
+    config: &TestConfig,
+    params: S::Params,
+) -> Result<Setup<S>, Failure<S::Error>> {
+    let state = within(Phase::Before, config.before_timeout, async {
+        S::env().await?;
+        S::before_with(params).await
+    })
+    .await?;
+
+    Ok((state, CleanupStack::new(), Resources::new()))
+}
+
+/// Await a test task, capturing panics so that teardown can run before they are re-raised
+async fn exercise<F, R, E>(
+    config: &TestConfig,
+    assertions: Arc<AtomicUsize>,
+    task: F,
+) -> Result<Result<R, Failure<E>>, Box<dyn Any + Send>>
+where
+    F: Future<Output = Result<R, E>>,
+{
+    let task = counted(Arc::clone(&assertions), task);
+    let exercised = catch_unwind(within(Phase::Test, config.test_timeout, task)).await;
+    let count = assertions.load(Ordering::SeqCst);
+
+    match exercised {
+        Ok(Ok(_)) if count < config.min_assertions => {
+            Ok(Err(Failure::Assertions(AssertionError::new(format!(
+                "expected at least {} assertions, but the task made {}",
+                config.min_assertions, count
+            )))))
+        }
+        exercised => exercised,
+    }
+}
+
+/// Alternative to running after() inline when tearing down a test
+pub(crate) enum AfterRunner<'a, S: Test> {
+    /// Hand the state of a passing test to a background runner
+    Background(&'a dyn Background<S>),
+    /// Tear down every test through a collector that keeps what each teardown reports
+    Collector(&'a dyn Collector<S>),
+}
+
+/// Run actions deferred by the task, shut down and tear down the test's state with the task's output and outcome,
+/// then run any clean-up registered during setup
+async fn teardown<S, R>(
+    config: &TestConfig,
+    state: &S,
+    output: Option<&R>,
+    outcome: &TestOutcome<S::Error>,
+    deferred: CleanupStack<S::Error>,
+    cleanup: CleanupStack<S::Error>,
+    runner: Option<(&Arc<S>, &AfterRunner<'_, S>)>,
+) -> Result<(), Failure<S::Error>>
+where
+    S: Test,
+    R: Any + Send + Sync,
+{
+    let deferred = deferred.run().await.map_err(Failure::Error);
+    let shutdown = state.shutdown().await.map_err(Failure::Error);
+
+    if let (Some((state, AfterRunner::Background(background))), TestOutcome::Passed) =
+        (runner, outcome)
+    {
+        if config.after_policy.runs_after(outcome) {
+            background.detach(Arc::clone(state), cleanup);
+
+            return aggregate::<S>(deferred, shutdown);
+        }
+    }
+
+    let after = if !config.after_policy.runs_after(outcome) {
+        Ok(())
+    } else {
+        match (runner, outcome) {
+            (Some((state, AfterRunner::Collector(collector))), _) => {
+                within(Phase::After, config.after_timeout, collector.after(state)).await
+            }
+            _ => {
+                within(
+                    Phase::After,
+                    config.after_timeout,
+                    state.after_with(output, outcome),
+                )
+                .await
+            }
+        }
+    };
+    let cleanup = cleanup.run().await.map_err(Failure::Error);
+
+    aggregate::<S>(
+        aggregate::<S>(aggregate::<S>(deferred, shutdown), after),
+        cleanup,
+    )
+}
+
+/// Tear down the state of a passing test in the background, without the task's output, then run any clean-up
+/// registered during setup
+pub(crate) async fn detached_after<S: Test>(
+    after_timeout: Option<Duration>,
+    state: Arc<S>,
+    cleanup: CleanupStack<S::Error>,
+) -> Result<(), Failure<S::Error>> {
+    let outcome = TestOutcome::Passed;
+    let after = within(
+        Phase::After,
+        after_timeout,
+        state.after_with::<()>(None, &outcome),
+    )
+    .await;
+    let cleanup = cleanup.run().await.map_err(Failure::Error);
+
+    aggregate::<S>(after, cleanup)
+}
+
+/// Keep every teardown failure, combining errors with the test's own aggregation
+fn aggregate<S: Test>(
+    first: Result<(), Failure<S::Error>>,
+    later: Result<(), Failure<S::Error>>,
+) -> Result<(), Failure<S::Error>> {
+    match (first, later) {
+        (Err(Failure::Error(first)), Err(Failure::Error(later))) => {
+            Err(Failure::Error(S::combine_errors(first, later)))
+        }
+        (first, later) => first.and(later),
+    }
+}
+
+/// Await a task between the state's before_each() and after_each(), running after_each() even if the task
+/// fails or panics
+pub(crate) async fn around_each<S, F>(
+    state: &S,
+    task: F,
+) -> Result<Result<(), S::Error>, Box<dyn Any + Send>>
+where
+    S: Test,
+    F: Future<Output = Result<(), S::Error>>,
+{
+    let task = async { task.await.map_err(Failure::Error) };
+
+    around_each_failure(state, task)
+        .await
+        .map(|result| result.map_err(Failure::into_error))
+}
+
+/// Await a task that may fail with any failure, like timing out, between the state's before_each() and
+/// after_each(), running after_each() even if the task fails or panics
+async fn around_each_failure<S, F>(
+    state: &S,
+    task: F,
+) -> Result<Result<(), Failure<S::Error>>, Box<dyn Any + Send>>
+where
+    S: Test,
+    F: Future<Output = Result<(), Failure<S::Error>>>,
+{
+    if let Err(error) = state.before_each().await {
+        return Ok(Err(Failure::Error(error)));
+    }
+
+    let result = catch_unwind(task).await;
+    let after_each = state.after_each().await.map_err(Failure::Error);
+
+    result.map(|result| aggregate::<S>(result, after_each))
+}
+
+/// Run read-only tasks sequentially against one state, each between before_each() and after_each() and within
+/// its own optional deadline, describing every failure
+pub(crate) async fn each<S, F, T>(
+    state: &Arc<S>,
+    timeout: Option<Duration>,
+    tasks: Vec<T>,
+) -> Vec<String>
+where
+    S: Test,
+    F: Future<Output = Result<(), S::Error>>,
+    T: FnOnce(Arc<S>) -> F,
+{
+    let mut failures = Vec::new();
+
+    for (index, task) in tasks.into_iter().enumerate() {
+        let task = within(Phase::Test, timeout, task(Arc::clone(state)));
+        let failure = match around_each_failure(&**state, task).await {
+            Err(payload) => panic_failure::<S>(&*payload),
+            Ok(Err(failure)) => failure.message::<S>(),
+            Ok(Ok(())) => continue,
+        };
+
+        failures.push(format!("task {} failed: {}", index, failure));
+    }
+
+    failures
+}
+
+/// Run read-only tasks sequentially against one state built by a single before() and torn down by a single
+/// after(), enforcing the config's test deadline on each task and describing every failure
+pub(crate) async fn shared<S, F, T>(config: &TestConfig, tasks: Vec<T>) -> Vec<String>
+where
+    S: Test,
+    F: Future<Output = Result<(), S::Error>> + Send,
+    T: Send + FnOnce(Arc<S>) -> F,
+{
+    let timeout = config.test_timeout;
+    let config = TestConfig {
+        test_timeout: None,
+        ..config.clone()
+    };
+    let run = run::<S, _, _, _>(&config, |state| async move {
+        Ok(each(&state, timeout, tasks).await)
+    })
+    .await;
+
+    let mut failures = match run.output {
+        Err(failure) => vec![failure.message::<S>()],
+        Ok(failures) => failures,
+    };
+
+    if let Err(failure) = run.teardown {
+        failures.push(failure.message::<S>());
+    }
+
+    failures
+}
+
+/// Shared state built by setup, along with the clean-up it registered and the resources it inserted
+type SharedSetup<S> = (Arc<S>, CleanupStack<<S as Test>::Error>, Resources);
+
+/// Process-wide state of one test type, built through setup on first use and kept until it's released
+struct Persistent<S: Test> {
+    building: Gate,
+    state: Mutex<Option<SharedSetup<S>>>,
+}
+
+impl<S: Test + 'static> Persistent<S> {
+    /// The slot of the test's type, holding the lock over every type's slot only long enough to find it,
+    /// so that building one type's state doesn't hold up the others
+    fn slot() -> Arc<Self> {
+        static SLOTS: OnceLock<Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>> =
+            OnceLock::new();
+
+        let slot = SLOTS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap()
+            .entry(TypeId::of::<S>())
+            .or_insert_with(|| {
+                Arc::new(Self {
+                    building: Gate::new(1),
+                    state: Mutex::new(None),
+                })
+            })
+            .clone();
+
+        slot.downcast()
+            .unwrap_or_else(|_| unreachable!("persistent states are keyed by type"))
+    }
+}
+
+/// Get the process-wide state of a test, building it through setup on first use.
+/// Setup that skips the test or fails leaves nothing behind, so that the next run tries again
+pub(crate) async fn persistent<S>(config: &TestConfig) -> Result<Arc<S>, Failure<S::Error>>
+where
+    S: Test + 'static,
+{
+    let slot = Persistent::<S>::slot();
+    let _permit = slot.building.acquire().await;
+
+    if let Some((state, _, _)) = &*slot.state.lock().unwrap() {
+        return Ok(Arc::clone(state));
+    }
+
+    let (state, cleanup, resources) = setup::<S>(config).await?;
+    let state = Arc::new(state);
+
+    *slot.state.lock().unwrap() = Some((Arc::clone(&state), cleanup, resources));
+
+    Ok(state)
+}
+
+/// Tear down the process-wide state of a test with after(), then run the clean-up its setup registered,
+/// if it was built
+pub(crate) async fn release_persistent<S>() -> Result<(), S::Error>
+where
+    S: Test + 'static,
+{
+    let slot = Persistent::<S>::slot();
+    let _permit = slot.building.acquire().await;
+    let state = slot.state.lock().unwrap().take();
+
+    let Some((state, cleanup, resources)) = state else {
+        return Ok(());
+    };
+
+    let after = state.after().await;
+    let cleanup = cleanup.run().await;
+    drop(resources);
+
+    match (after, cleanup) {
+        (Err(error), Err(later)) => Err(S::combine_errors(error, later)),
+        (after, cleanup) => after.and(cleanup),
+    }
+}
+
+/// Describe a panic caught from a task that shares its state with others
+pub(crate) fn panic_failure<S: Test>(payload: &(dyn Any + Send)) -> String {
+    match S::panic_to_error(payload) {
+        Some(error) => S::fmt_failure(&error),
+        None => format!("panicked: {}", panic_message(payload)),
+    }
+}
+
+/// Result of a task, split into its output, its outcome, and any panic to re-raise after teardown
+struct Exercised<R, E> {
+    output: Option<R>,
+    outcome: TestOutcome<E>,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<R, E> Exercised<R, E> {
+    fn new<S>(result: Result<Result<R, Failure<E>>, Box<dyn Any + Send>>) -> Self
+    where
+        S: Test<Error = E>,
+    {
+        let (output, outcome, panic) = match result {
+            Err(payload) => match S::panic_to_error(&*payload) {
+                Some(error) => (None, TestOutcome::Failed(error), None),
+                None => (
+                    None,
+                    TestOutcome::Panicked(panic_message(&*payload)),
+                    Some(payload),
+                ),
+            },
+            Ok(Err(Failure::Error(error))) => (None, TestOutcome::Failed(error), None),
+            Ok(Err(Failure::TimedOut(_, duration))) => {
+                (None, TestOutcome::TimedOut(duration), None)
+            }
+            Ok(Err(Failure::Skipped(_))) => unreachable!("only setup can skip a test"),
+            Ok(Err(Failure::Assertions(error))) => match S::assertion_error(error.clone()) {
+                Some(error) => (None, TestOutcome::Failed(error), None),
+                None => (
+                    None,
+                    TestOutcome::Panicked(error.to_string()),
+                    Some(Box::new(error.to_string()) as Box<dyn Any + Send>),
+                ),
+            },
+            Ok(Ok(output)) => (Some(output), TestOutcome::Passed, None),
+        };
+
+        Self {
+            output,
+            outcome,
+            panic,
+        }
+    }
+
+    /// Treat a task that ended itself early by returning the `CompletedEarly` sentinel as passing, if it had
+    /// nothing to produce. Only call this while the failure holds the sentinel
+    fn completed_early(self) -> Self
+    where
+        R: Any,
+    {
+        match (&self.output, &self.outcome, unit_output()) {
+            (None, TestOutcome::Failed(_), Some(output)) => Self {
+                output: Some(output),
+                outcome: TestOutcome::Passed,
+                panic: None,
+            },
+            _ => self,
+        }
+    }
+
+    /// Let the fixture add diagnostics to the error of a failed task while its resources are still live
+    async fn enrich<S>(self, state: &S) -> Self
+    where
+        S: Test<Error = E>,
+    {
+        match self.outcome {
+            TestOutcome::Failed(error) => Self {
+                outcome: TestOutcome::Failed(state.enrich_error(error).await),
+                ..self
+            },
+            outcome => Self { outcome, ..self },
+        }
+    }
+
+    /// Combine the task's output with its teardown, re-raising any panic from the task
+    fn finish(self, teardown: Result<(), Failure<E>>, timings: Timings) -> Run<R, E> {
+        if let Some(payload) = self.panic {
+            panic::resume_unwind(payload);
+        }
+
+        let output = match (self.output, self.outcome) {
+            (Some(output), _) => Ok(output),
+            (None, TestOutcome::Failed(error)) => Err(Failure::Error(error)),
+            (None, TestOutcome::TimedOut(duration)) => {
+                Err(Failure::TimedOut(Phase::Test, duration))
+            }
+            (None, _) => unreachable!("task finished without an output or a failure"),
+        };
+
+        Run {
+            output,
+            teardown,
+            timings,
+            steps: Vec::new(),
+            logs: Vec::new(),
+            assertions: 0,
+        }
+    }
+}
+
+/// Run the before -> task -> after lifecycle of a test, sharing the state with the task
+pub(crate) async fn run<S, R, F, T>(config: &TestConfig, task: T) -> Run<R, S::Error>
+where
+    S: Test,
+    R: Any + Send + Sync,
+    F: Future<Output = Result<R, S::Error>> + Send,
+    T: Send + FnOnce(Arc<S>) -> F,
+{
+    run_with_context::<S, _, _, _>(config, |context| task(Arc::clone(context.state()))).await
+}
+
+/// Run the before -> task -> after lifecycle of a test, handing the task a context around the state
+pub(crate) async fn run_with_context<S, R, F, T>(config: &TestConfig, task: T) -> Run<R, S::Error>
+where
+    S: Test,
+    R: Any + Send + Sync,
+    F: Future<Output = Result<R, S::Error>> + Send,
+    T: Send + FnOnce(Context<S>) -> F,
+{
+    run_with_metadata::<S, _, _, _>(config, Metadata::default(), task).await
+}
+
+/// Run the before -> task -> after lifecycle of a test, handing the task a context that describes the run
+pub(crate) async fn run_with_metadata<S, R, F, T>(
+    config: &TestConfig,
+    metadata: Metadata,
+    task: T,
+) -> Run<R, S::Error>
+where
+    S: Test,
+    R: Any + Send + Sync,
+    F: Future<Output = Result<R, S::Error>> + Send,
+    T: Send + FnOnce(Context<S>) -> F,
+{
+    run_from(config, setup::<S>(config), task, None, metadata).await
+}
+
+/// Run the before -> task -> after lifecycle of a test with state built from runtime parameters
+pub(crate) async fn run_with_params<S, R, F, T>(
+    config: &TestConfig,
+    params: S::Params,
+    task: T,
+) -> Run<R, S::Error>
+where
+    S: Parameterized,
+    R: Any + Send + Sync,
+    F: Future<Output = Result<R, S::Error>> + Send,
+    T: Send + FnOnce(Context<S>) -> F,
+{
+    run_from(
+        config,
+        setup_with::<S>(config, params),
+        task,
+        None,
+        Metadata::default(),
+    )
+    .await
+}
+
+/// Run the task -> after lifecycle of a test once the setup future builds its state, optionally handing the state
+/// to another runner for its after()
+async fn run_from<S, R, F, T, B>(
+    config: &TestConfig,
+    setup: B,
+    task: T,
+    runner: Option<&AfterRunner<'_, S>>,
+    metadata: Metadata,
+) -> Run<R, S::Error>
+where
+    S: Test,
+    R: Any + Send + Sync,
+    F: Future<Output = Result<R, S::Error>> + Send,
+    T: Send + FnOnce(Context<S>) -> F,
+    B: Future<Output = Result<Setup<S>, Failure<S::Error>>>,
+{
+    silenced(config.suppress_panic_output, async {
+        let mut timings = Timings::default();
+        let (setup, before) = timed(setup).await;
+        timings.before = before;
+        check_slow_before::<S>(config, before);
+
+        let (state, cleanup, resources) = match setup {
+            Err(failure) => return Run::setup_failed(failure, timings),
+            Ok(setup) => setup,
+        };
+
+        let deadline = config.test_timeout.map(Deadline::after);
+        let context = Context::new(Arc::new(state), deadline, resources, metadata);
+        let (exercised, test) = timed(exercise(
+            config,
+            Arc::clone(context.assertions()),
+            task(context.clone()),
+        ))
+        .await;
+        let mut exercised = Exercised::new::<S>(exercised);
+        timings.test = test;
+        context.cancel_token().cancel();
+
+        if context.completed_early() {
+            exercised = exercised.completed_early();
+        }
+
+        let exercised = exercised.enrich(&**context.state()).await;
+
+        let output = exercised.output.as_ref();
+        let (teardown, after) = timed(teardown(
+            config,
+            &*context,
+            output,
+            &exercised.outcome,
+            context.take_deferred(),
+            cleanup,
+            runner.map(|runner| (context.state(), runner)),
+        ))
+        .await;
+        timings.after = after;
+
+        let mut run = exercised.finish(teardown, timings);
+        run.steps = context.take_steps();
+        run.logs = context.take_logs();
+        run.assertions = context.assertion_count();
+
+        run
+    })
+    .await
+}
+
+/// Run a series of tasks, each with its own lifecycle, between before_all and after_all, waiting for every
+/// teardown left to a background runner before after_all, or handing a collector's reports to after_all
+pub(crate) async fn suite<S, I, F, T>(
+    config: &TestConfig,
+    runner: Option<&AfterRunner<'_, S>>,
+    tasks: I,
+) where
+    S: Test,
+    I: IntoIterator<Item = T>,
+    F: Future<Output = Result<(), S::Error>> + Send,
+    T: Send + FnOnce(Arc<S>) -> F,
+{
+    if let Err(error) = S::before_all().await {
+        panic!("{}", S::fmt_failure(&error));
+    }
+
+    let mut failures = Vec::new();
+    let mut panicked = None;
+
+    for task in tasks {
+        let task = |context: Context<S>| async move {
+            let state = Arc::clone(context.state());
+
+            match around_each(&*state, task(Arc::clone(&state))).await {
+                Err(payload) => panic::resume_unwind(payload),
+                Ok(result) => result,
+            }
+        };
+
+        match catch_unwind(run_from(
+            config,
+            setup::<S>(config),
+            task,
+            runner,
+            Metadata::default(),
+        ))
+        .await
+        {
+            Err(payload) => {
+                panicked.get_or_insert(payload);
+            }
+            Ok(run) => {
+                if let Err(message) = run.into_message_result::<S>() {
+                    failures.push(message);
+                }
+            }
+        }
+    }
+
+    if let Some(AfterRunner::Background(background)) = runner {
+        failures.extend(background.join().await);
+    }
+
+    let after_all = match runner {
+        Some(AfterRunner::Collector(collector)) => collector.after_all().await,
+        _ => S::after_all().await,
+    };
+
+    if let Err(error) = after_all {
+        failures.push(S::fmt_failure(&error));
+    }
+
+    if let Some(payload) = panicked {
+        panic::resume_unwind(payload);
+    }
+
+    if !failures.is_empty() {
+        panic!("{}", failures.join("\n"));
+    }
+}
+
+/// Run the before -> task -> after lifecycle of a test, lending the task exclusive access to the state
+pub(crate) async fn run_mut<S, R, T>(config: &TestConfig, task: T) -> Run<R, S::Error>
+where
+    S: Test,
+    R: Any + Send + Sync,
+    T: Send + for<'a> FnOnce(&'a mut S) -> BoxFuture<'a, Result<R, S::Error>>,
+{
+    silenced(config.suppress_panic_output, async {
+        let mut timings = Timings::default();
+        let (setup, before) = timed(setup::<S>(config)).await;
+        timings.before = before;
+        check_slow_before::<S>(config, before);
+
+        let (mut state, cleanup, _) = match setup {
+            Err(failure) => return Run::setup_failed(failure, timings),
+            Ok(setup) => setup,
+        };
+
+        let assertions = Arc::new(AtomicUsize::new(0));
+        let (exercised, test) =
+            timed(exercise(config, Arc::clone(&assertions), task(&mut state))).await;
+        let exercised = Exercised::new::<S>(exercised).enrich(&state).await;
+        timings.test = test;
+
+        let output = exercised.output.as_ref();
+        let (teardown, after) = timed(teardown(
+            config,
+            &state,
+            output,
+            &exercised.outcome,
+            CleanupStack::new(),
+            cleanup,
+            None,
+        ))
+        .await;
+        timings.after = after;
+
+        exercised.finish(teardown, timings)
+    })
+    .await
+}
+
+impl<R: Any, E> Run<R, E> {
+    /// Run whose state could not be built, leaving nothing to tear down
+    fn setup_failed(failure: Failure<E>, timings: Timings) -> Self {
+        Self {
+            output: Err(failure),
+            teardown: Ok(()),
+            timings,
+            steps: Vec::new(),
+            logs: Vec::new(),
+            assertions: 0,
+        }
+    }
+
+    /// Summarize the run's timings and final outcome
+    pub(crate) fn into_report(mut self) -> TestReport<E> {
+        let timings = self.timings;
+
+        TestReport {
+            name: None,
+            before_duration: timings.before,
+            test_duration: timings.test,
+            after_duration: timings.after,
+            steps: mem::take(&mut self.steps),
+            assertion_count: self.assertions,
+            outcome: self.into_outcome(),
+        }
+    }
+
+    /// Reduce the run to its final outcome, including any failure during setup or teardown
+    pub(crate) fn into_outcome(self) -> TestOutcome<E> {
+        if let Err(Failure::Skipped(_)) = self.output {
+            return TestOutcome::Skipped;
+        }
+
+        match self.into_result() {
+            Err(failure) => failure.into_outcome(),
+            Ok(_) => TestOutcome::Passed,
+        }
+    }
+
+    /// Flatten a run into its output or the message of its first failure,
+    /// attributing a failed task to the last step that failed and appending any logs buffered by the task
+    pub(crate) fn into_message_result<S>(self) -> Result<R, String>
+    where
+        S: Test<Error = E>,
+    {
+        let step = self.steps.iter().rev().find(|step| !step.passed);
+
+        let mut message = match (self.output, self.teardown) {
+            (Ok(output), Ok(())) => return Ok(output),
+            (Err(Failure::Skipped(reason)), _) => {
+                return unit_output().ok_or_else(|| Failure::<E>::Skipped(reason).message::<S>())
+            }
+            (Ok(_), Err(failure)) => failure.message::<S>(),
+            (Err(failure), _) => match step {
+                Some(step) => format!("step \"{}\" failed: {}", step.name, failure.message::<S>()),
+                None => failure.message::<S>(),
+            },
+        };
+
+        if !self.logs.is_empty() {
+            message.push_str("\n\n---- logs ----\n");
+            message.push_str(&self.logs.join("\n"));
+        }
+
+        Err(message)
+    }
+
+    /// Flatten a run into its output or first failure, treating a skipped run as passing if its task had
+    /// no output to produce
+    pub(crate) fn into_result(self) -> Result<R, Failure<E>> {
+        let output = match self.output {
+            Err(Failure::Skipped(reason)) => return unit_output().ok_or(Failure::Skipped(reason)),
+            output => output?,
+        };
+        self.teardown?;
+
+        Ok(output)
+    }
+
+    /// Flatten a run into its output, none if setup skipped it, or its first failure
+    pub(crate) fn into_output(self) -> Result<Option<R>, Failure<E>> {
+        let output = match self.output {
+            Err(Failure::Skipped(_)) => return Ok(None),
+            output => output?,
+        };
+        self.teardown?;
+
+        Ok(Some(output))
+    }
+}
+
+/// Output of a task that was skipped or ended early, which only exists if the task had nothing to produce
+fn unit_output<R: Any>() -> Option<R> {
+    let unit: Box<dyn Any> = Box::new(());
+
+    unit.downcast().ok().map(|output| *output)
+}
+
+/// Run a Result-emitting task without deadlines, describing its failure or panic if it didn't pass
+#[cfg(feature = "proptest")]
+pub(crate) async fn check<S, F, T>(task: T) -> Option<String>
+where
+    S: Test,
+    F: Future<Output = Result<(), S::Error>> + Send,
+    T: Send + FnOnce(Arc<S>) -> F,
+{
+    match catch_unwind(try_run::<S, _, _>(task)).await {
+        Err(payload) => Some(panic_failure::<S>(&*payload)),
+        Ok(Err(error)) => Some(S::fmt_failure(&error)),
+        Ok(Ok(())) => None,
+    }
+}
+
+/// Run a Result-emitting task without deadlines, returning the first error encountered
+pub(crate) async fn try_run<S, F, T>(task: T) -> Result<(), S::Error>
+where
+    S: Test,
+    F: Future<Output = Result<(), S::Error>> + Send,
+    T: Send + FnOnce(Arc<S>) -> F,
+{
+    try_run_returning::<S, _, _, _>(task).await.map(drop)
+}
+
+/// Run a task that produces an output without deadlines, returning its output, none if setup skipped the
+/// test, or the first error encountered
+pub(crate) async fn try_run_returning<S, R, F, T>(task: T) -> Result<Option<R>, S::Error>
+where
+    S: Test,
+    R: Any + Send + Sync,
+    F: Future<Output = Result<R, S::Error>> + Send,
+    T: Send + FnOnce(Arc<S>) -> F,
+{
+    run::<S, _, _, _>(&TestConfig::default(), task)
+        .await
+        .into_output()
+        .map_err(Failure::into_error)
+}
@@ -0,0 +1,171 @@
+use crate::{executor::block_on, unwind::catch_unwind};
+use async_trait::async_trait;
+use std::{future::Future, ops::Deref, panic, rc::Rc, sync::Arc};
+
+/// Reference-counted handle that a `LocalTest`'s state is shared with its task through. Implemented for `Rc`
+/// (the default for `LocalTest`) and `Arc`, and implementable for custom handles.
+///
+/// Only `LocalTest` is generic over its handle: `Test` drives `Send` tasks that may hop between threads, so its
+/// state is always shared through an `Arc`
+///
+/// ```
+/// use spekt::{LocalTest, Shared};
+/// use std::{cell::RefCell, ops::Deref, rc::Rc};
+///
+/// struct Session {
+///     history: Rc<RefCell<Vec<String>>>,
+/// }
+///
+/// #[spekt::async_trait(?Send)]
+/// impl LocalTest for Session {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self {
+///             history: Rc::new(RefCell::new(Vec::new())),
+///         })
+///     }
+/// }
+///
+/// /// Handle that counts how many times the state was shared
+/// struct Counted<T>(Rc<T>, Rc<()>);
+///
+/// impl<T> Clone for Counted<T> {
+///     fn clone(&self) -> Self {
+///         Self(Rc::clone(&self.0), Rc::clone(&self.1))
+///     }
+/// }
+///
+/// impl<T> Deref for Counted<T> {
+///     type Target = T;
+///
+///     fn deref(&self) -> &T {
+///         &self.0
+///     }
+/// }
+///
+/// impl<T> Shared<T> for Counted<T> {
+///     fn share(state: T) -> Self {
+///         Self(Rc::new(state), Rc::new(()))
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// Session::test(|session: Rc<Session>| async move {
+///     session.history.borrow_mut().push("login".to_string());
+///
+///     Ok(())
+/// })
+/// .await;
+///
+/// Session::test_with(|session: Counted<Session>| async move {
+///     session.history.borrow_mut().push("login".to_string());
+///     anyhow::ensure!(Rc::strong_count(&session.1) == 2, "expected the runner's handle and the task's");
+///
+///     Ok(())
+/// })
+/// .await;
+/// # }
+/// ```
+pub trait Shared<T>: Clone + Deref<Target = T> {
+    /// Move a test's state behind a new handle
+    fn share(state: T) -> Self;
+}
+
+impl<T> Shared<T> for Rc<T> {
+    fn share(state: T) -> Self {
+        Rc::new(state)
+    }
+}
+
+impl<T> Shared<T> for Arc<T> {
+    fn share(state: T) -> Self {
+        Arc::new(state)
+    }
+}
+
+/// Test-running trait to handle the lifecycles of single-threaded, `!Send` test state
+#[async_trait(?Send)]
+pub trait LocalTest
+where
+    Self: Sized,
+{
+    /// The format-able error shared by each step. anyhow::Error is recommended!
+    type Error: std::fmt::Display;
+
+    /// Initialize test suite with new instance of test's state
+    async fn before() -> Result<Self, Self::Error>;
+
+    /// Optionally clean up after test run
+    async fn after(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Format an error for a failure message. Override to print source chains or alternate forms
+    fn fmt_failure(error: &Self::Error) -> String {
+        format!("{}", error)
+    }
+
+    /// Run a Result-emitting test task, returning the first error encountered
+    async fn try_test<F, T>(task: T) -> Result<(), Self::Error>
+    where
+        F: Future<Output = Result<(), Self::Error>>,
+        T: FnOnce(Rc<Self>) -> F,
+    {
+        Self::try_test_with(task).await
+    }
+
+    /// Run a Result-emitting test task that shares the test's state through a handle of its choosing,
+    /// returning the first error encountered
+    async fn try_test_with<P, F, T>(task: T) -> Result<(), Self::Error>
+    where
+        P: Shared<Self>,
+        F: Future<Output = Result<(), Self::Error>>,
+        T: FnOnce(P) -> F,
+    {
+        let state = P::share(Self::before().await?);
+        let test_run = catch_unwind(task(P::clone(&state))).await;
+        let after = state.after().await;
+
+        match test_run {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(test_run) => test_run.and(after),
+        }
+    }
+
+    /// Run a Result-emitting test task, handling assertion errors gracefully
+    async fn test<F, T>(task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>>,
+        T: FnOnce(Rc<Self>) -> F,
+    {
+        if let Err(error) = Self::try_test(task).await {
+            panic!("{}", Self::fmt_failure(&error));
+        }
+    }
+
+    /// Run a Result-emitting test task that shares the test's state through a handle of its choosing,
+    /// handling assertion errors gracefully
+    async fn test_with<P, F, T>(task: T)
+    where
+        P: Shared<Self>,
+        F: Future<Output = Result<(), Self::Error>>,
+        T: FnOnce(P) -> F,
+    {
+        if let Err(error) = Self::try_test_with(task).await {
+            panic!("{}", Self::fmt_failure(&error));
+        }
+    }
+
+    /// Run a Result-emitting test task to completion on the current thread, without an async runtime
+    fn test_blocking<F, T>(task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>>,
+        T: FnOnce(Rc<Self>) -> F,
+    {
+        if let Err(error) = block_on(Self::test(task)) {
+            panic!("{}", error);
+        }
+    }
+}
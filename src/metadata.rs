@@ -0,0 +1,76 @@
+use crate::{context::Context, test::Test};
+
+/// Where a single run of a task sits in its test: the name it runs under, which attempt it is, and the parameter
+/// it was given, for logging or naming resources uniquely
+///
+/// ```
+/// use spekt::{Test, TestConfig};
+/// use std::sync::Mutex;
+///
+/// static ATTEMPTS: Mutex<Vec<(usize, usize)>> = Mutex::new(Vec::new());
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let config = TestConfig::builder().retries(2).build();
+///
+/// Fixture::test_with_metadata("eventually_consistent", config, |context| async move {
+///     let metadata = context.metadata();
+///
+///     assert_eq!(metadata.name.as_deref(), Some("eventually_consistent"));
+///     ATTEMPTS.lock().unwrap().push((metadata.attempt, metadata.total_attempts));
+///     anyhow::ensure!(metadata.attempt == 3, "not consistent yet");
+///
+///     Ok(())
+/// })
+/// .await;
+///
+/// assert_eq!(*ATTEMPTS.lock().unwrap(), [(1, 3), (2, 3), (3, 3)]);
+///
+/// Fixture::test_each_with_context(["alice", "bob"], |context, user| async move {
+///     assert_eq!(context.metadata().param_debug, Some(format!("{:?}", user)));
+///
+///     Ok(())
+/// })
+/// .await;
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Metadata {
+    /// Name the test runs under, if any
+    pub name: Option<String>,
+    /// Which attempt this run is, starting from 1
+    pub attempt: usize,
+    /// Total number of attempts the test may make
+    pub total_attempts: usize,
+    /// `Debug` representation of the parameter the task was given, if any
+    pub param_debug: Option<String>,
+}
+
+impl Default for Metadata {
+    fn default() -> Self {
+        Self {
+            name: None,
+            attempt: 1,
+            total_attempts: 1,
+            param_debug: None,
+        }
+    }
+}
+
+impl<S: Test> Context<S> {
+    /// Name, attempt and parameter of the current run
+    pub fn metadata(&self) -> &Metadata {
+        self.run_metadata()
+    }
+}
@@ -0,0 +1,30 @@
+use std::{
+    io,
+    net::{Ipv4Addr, SocketAddr, TcpListener},
+};
+
+/// Bind a listener to an OS-assigned port on localhost, handing the bound listener straight to a server.
+/// Unlike `free_port`, the port stays reserved for as long as the listener is held
+///
+/// ```
+/// let first = spekt::reserved_listener()?;
+/// let second = spekt::reserved_listener()?;
+///
+/// assert_ne!(first.local_addr()?.port(), second.local_addr()?.port());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn reserved_listener() -> io::Result<TcpListener> {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+}
+
+/// Find a localhost address with an unused port for a server fixture to bind to.
+/// The port is released before this returns, so another process could claim it before the fixture binds it;
+/// prefer `reserved_listener` for servers that accept an already-bound listener
+pub fn free_addr() -> io::Result<SocketAddr> {
+    reserved_listener()?.local_addr()
+}
+
+/// Find an unused port on localhost, with the same race as `free_addr`
+pub fn free_port() -> io::Result<u16> {
+    Ok(free_addr()?.port())
+}
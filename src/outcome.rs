@@ -0,0 +1,145 @@
+use std::time::Duration;
+
+/// Final outcome of a test task
+#[derive(Debug, PartialEq, Eq)]
+pub enum TestOutcome<E> {
+    /// The task completed successfully
+    Passed,
+    /// The task returned an error
+    Failed(E),
+    /// The task didn't complete within its deadline
+    TimedOut(Duration),
+    /// The task panicked with the given message
+    Panicked(String),
+    /// The test was skipped before its task ran, either up front or by `Test::before_or_skip`
+    Skipped,
+    /// Every attempt of a test allowed to be flaky failed, with the error of each attempt
+    Flaky(Vec<E>),
+}
+
+impl<E> TestOutcome<E> {
+    /// Check if the task completed successfully
+    pub fn is_passed(&self) -> bool {
+        matches!(self, Self::Passed)
+    }
+
+    /// Check if the test was skipped
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, Self::Skipped)
+    }
+
+    /// Check if every attempt of a test allowed to be flaky failed
+    pub fn is_flaky(&self) -> bool {
+        matches!(self, Self::Flaky(_))
+    }
+
+    /// Convert the errors of a failed or flaky outcome, leaving every other outcome as-is
+    pub fn map_failure<F>(self, mut map: impl FnMut(E) -> F) -> TestOutcome<F> {
+        match self {
+            Self::Passed => TestOutcome::Passed,
+            Self::Failed(error) => TestOutcome::Failed(map(error)),
+            Self::TimedOut(duration) => TestOutcome::TimedOut(duration),
+            Self::Panicked(message) => TestOutcome::Panicked(message),
+            Self::Skipped => TestOutcome::Skipped,
+            Self::Flaky(errors) => TestOutcome::Flaky(errors.into_iter().map(&mut map).collect()),
+        }
+    }
+}
+
+/// Result of setting up a test's state: either ready for the task, or skipped because prerequisites are missing
+///
+/// ```
+/// use spekt::{BeforeOutcome, CleanupStack, Resources, Test, TestOutcome};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// static TASK_RAN: AtomicBool = AtomicBool::new(false);
+/// static AFTER_RAN: AtomicBool = AtomicBool::new(false);
+///
+/// struct Gpu;
+///
+/// #[spekt::async_trait]
+/// impl Test for Gpu {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+///
+///     async fn before_or_skip(
+///         _cleanup: &mut CleanupStack<Self::Error>,
+///         _resources: &mut Resources,
+///     ) -> Result<BeforeOutcome<Self>, Self::Error> {
+///         Ok(BeforeOutcome::Skip("no GPU available".to_string()))
+///     }
+///
+///     async fn after(&self) -> Result<(), Self::Error> {
+///         AFTER_RAN.store(true, Ordering::SeqCst);
+///
+///         Ok(())
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let outcome = Gpu::run(|_| async {
+///     TASK_RAN.store(true, Ordering::SeqCst);
+///
+///     Ok(())
+/// })
+/// .await;
+///
+/// assert!(outcome.is_skipped());
+/// assert!(!TASK_RAN.load(Ordering::SeqCst));
+/// assert!(!AFTER_RAN.load(Ordering::SeqCst));
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub enum BeforeOutcome<S> {
+    /// The state is ready for the task
+    Ready(S),
+    /// The test should be skipped for the given reason, without running the task or after()
+    Skip(String),
+}
+
+/// Timing and outcome of a single test run
+#[derive(Debug)]
+pub struct TestReport<E> {
+    /// Name of the test case, if it was run under one
+    pub name: Option<String>,
+    /// Time spent building the test's state in before()
+    pub before_duration: Duration,
+    /// Time spent running the test task itself
+    pub test_duration: Duration,
+    /// Time spent cleaning up in after()
+    pub after_duration: Duration,
+    /// Every step recorded by the task through `Context::step`, in the order they ran
+    pub steps: Vec<StepReport>,
+    /// Number of assertions the task made through spekt's own assertions, like `ensure!`
+    pub assertion_count: usize,
+    /// Final outcome of the run, including any failure during setup or teardown
+    pub outcome: TestOutcome<E>,
+}
+
+impl<E> TestReport<E> {
+    /// Attribute the report to a named test case
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Total time spent across every phase
+    pub fn total_duration(&self) -> Duration {
+        self.before_duration + self.test_duration + self.after_duration
+    }
+}
+
+/// Name, timing and result of a single step of a test task
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepReport {
+    /// Name the step was recorded under
+    pub name: String,
+    /// Time spent running the step
+    pub duration: Duration,
+    /// Whether the step completed successfully
+    pub passed: bool,
+}
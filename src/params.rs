@@ -0,0 +1,74 @@
+use crate::{config::TestConfig, lifecycle, test::Test};
+use async_trait::async_trait;
+use std::{future::Future, sync::Arc};
+
+/// Test state that can also be built from runtime parameters, so that the same fixture can be instantiated with
+/// different configurations (e.g. a different database name or feature set) instead of reading globals
+///
+/// ```
+/// use spekt::{Parameterized, Test};
+///
+/// struct Database {
+///     name: String,
+/// }
+///
+/// struct Params {
+///     name: &'static str,
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Test for Database {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Self::before_with(Params { name: "test" }).await
+///     }
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Parameterized for Database {
+///     type Params = Params;
+///
+///     async fn before_with(params: Self::Params) -> Result<Self, Self::Error> {
+///         Ok(Self {
+///             name: params.name.to_string(),
+///         })
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// Database::test_with_params(Params { name: "billing" }, |database| async move {
+///     assert_eq!(database.name, "billing");
+///
+///     Ok(())
+/// })
+/// .await;
+/// # }
+/// ```
+#[async_trait]
+pub trait Parameterized: Test {
+    /// Runtime parameters for building the test's state
+    type Params: Send;
+
+    /// Initialize a new instance of the test's state from parameters
+    async fn before_with(params: Self::Params) -> Result<Self, Self::Error>;
+
+    /// Run a Result-emitting test task against state built from the parameters with before_with
+    async fn test_with_params<F, T>(params: Self::Params, task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        let run = lifecycle::run_with_params::<Self, _, _, _>(
+            &TestConfig::default(),
+            params,
+            |context| task(Arc::clone(context.state())),
+        )
+        .await;
+
+        if let Err(message) = run.into_message_result::<Self>() {
+            panic!("{}", message);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::lifecycle::Timings;
+
+/// Check if stdout supports colors, respecting `NO_COLOR`
+#[cfg(feature = "color")]
+fn colored() -> bool {
+    let disabled = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+    !disabled && supports_color::on_cached(supports_color::Stream::Stdout).is_some()
+}
+
+/// Paint an error message red when stdout supports colors
+#[cfg(feature = "color")]
+pub(crate) fn error(message: String) -> String {
+    use owo_colors::OwoColorize;
+
+    if !colored() {
+        return message;
+    }
+
+    message.red().to_string()
+}
+
+/// Leave an error message as-is without colors
+#[cfg(not(feature = "color"))]
+pub(crate) fn error(message: String) -> String {
+    message
+}
+
+/// Format a failure as a block of a bold red header, the failure message, and the time spent in each phase,
+/// falling back to `header: message` when stdout doesn't support colors
+#[cfg(feature = "color")]
+pub(crate) fn failure(header: &str, message: &str, timings: Option<Timings>) -> String {
+    use owo_colors::OwoColorize;
+
+    if !colored() {
+        return format!("{}: {}", header, message);
+    }
+
+    let mut block = format!("{}\n{}", header.red().bold(), message);
+
+    if let Some(timings) = timings {
+        let durations = format!(
+            "before {:?}, test {:?}, after {:?}",
+            timings.before, timings.test, timings.after
+        );
+
+        block.push_str(&format!("\n{}", durations.dimmed()));
+    }
+
+    block
+}
+
+/// Format a failure as `header: message` without colors
+#[cfg(not(feature = "color"))]
+pub(crate) fn failure(header: &str, message: &str, _timings: Option<Timings>) -> String {
+    format!("{}: {}", header, message)
+}
@@ -0,0 +1,30 @@
+//! Reporters that serialize or summarize test outcomes for CI systems and other harnesses
+
+use crate::outcome::TestOutcome;
+use std::fmt;
+
+#[cfg(feature = "json")]
+#[deny(missing_docs, unreachable_pub)]
+mod json;
+#[deny(missing_docs, unreachable_pub)]
+mod junit;
+#[deny(missing_docs, unreachable_pub)]
+mod summary;
+#[deny(missing_docs, unreachable_pub)]
+mod tap;
+
+#[cfg(feature = "json")]
+pub use self::json::*;
+pub use self::junit::*;
+pub use self::summary::*;
+pub use self::tap::*;
+
+/// Describe why a test did not pass, if it failed
+fn failure_message<E: fmt::Display>(outcome: &TestOutcome<E>) -> Option<String> {
+    match outcome {
+        TestOutcome::Passed | TestOutcome::Skipped | TestOutcome::Flaky(_) => None,
+        TestOutcome::Failed(error) => Some(error.to_string()),
+        TestOutcome::TimedOut(duration) => Some(format!("timed out after {:?}", duration)),
+        TestOutcome::Panicked(message) => Some(format!("panicked: {}", message)),
+    }
+}
@@ -0,0 +1,107 @@
+use super::failure_message;
+use crate::outcome::{TestOutcome, TestReport};
+use serde::Serialize;
+use std::{fmt, io};
+
+/// Time spent in each phase of a single run, in seconds
+#[derive(Clone, Debug, Serialize)]
+struct Durations {
+    before: f64,
+    test: f64,
+    after: f64,
+    total: f64,
+}
+
+/// Single named run in a JSON lines report
+#[derive(Clone, Debug, Serialize)]
+struct Record {
+    name: String,
+    outcome: &'static str,
+    error: Option<String>,
+    durations: Durations,
+    attempts: usize,
+}
+
+/// JSON lines report of named test runs, one object per line, for streaming into log pipelines and
+/// custom dashboards
+///
+/// ```
+/// use spekt::{report::Json, TestOutcome, TestReport};
+/// use std::time::Duration;
+///
+/// let mut json = Json::new();
+///
+/// json.add_case(
+///     "rejects_duplicates",
+///     &TestReport {
+///         name: None,
+///         before_duration: Duration::from_millis(250),
+///         test_duration: Duration::from_millis(500),
+///         after_duration: Duration::from_millis(250),
+///         steps: Vec::new(),
+///         assertion_count: 0,
+///         outcome: TestOutcome::Failed("expected unique violation".to_string()),
+///     },
+/// );
+///
+/// let mut lines = Vec::new();
+///
+/// json.write(&mut lines)?;
+///
+/// assert_eq!(
+///     String::from_utf8(lines).unwrap(),
+///     r#"{"name":"rejects_duplicates","outcome":"failed","error":"expected unique violation","durations":{"before":0.25,"test":0.5,"after":0.25,"total":1.0},"attempts":1}
+/// "#
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Json {
+    records: Vec<Record>,
+}
+
+impl Json {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the report of a single named run. Flaky runs count one attempt per error, every other run one
+    pub fn add_case<E: fmt::Display>(&mut self, name: impl Into<String>, report: &TestReport<E>) {
+        let outcome = match &report.outcome {
+            TestOutcome::Passed => "passed",
+            TestOutcome::Failed(_) => "failed",
+            TestOutcome::TimedOut(_) => "timed_out",
+            TestOutcome::Panicked(_) => "panicked",
+            TestOutcome::Skipped => "skipped",
+            TestOutcome::Flaky(_) => "flaky",
+        };
+        let (error, attempts) = match &report.outcome {
+            TestOutcome::Flaky(errors) => (errors.last().map(ToString::to_string), errors.len()),
+            outcome => (failure_message(outcome), 1),
+        };
+
+        self.records.push(Record {
+            name: name.into(),
+            outcome,
+            error,
+            durations: Durations {
+                before: report.before_duration.as_secs_f64(),
+                test: report.test_duration.as_secs_f64(),
+                after: report.after_duration.as_secs_f64(),
+                total: report.total_duration().as_secs_f64(),
+            },
+            attempts,
+        });
+    }
+
+    /// Serialize every recorded run as a JSON object on its own line
+    pub fn write(&self, mut writer: impl io::Write) -> io::Result<()> {
+        for record in &self.records {
+            serde_json::to_writer(&mut writer, record)?;
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+use super::failure_message;
+use crate::outcome::{TestOutcome, TestReport};
+use std::{fmt, io, time::Duration};
+
+/// Result of a single case in a JUnit report
+#[derive(Clone, Debug)]
+enum Status {
+    Passed,
+    Failed(String),
+    Skipped,
+}
+
+/// Single named case in a JUnit report
+#[derive(Clone, Debug)]
+struct Case {
+    name: String,
+    duration: Duration,
+    status: Status,
+}
+
+/// JUnit XML report of a suite of test runs, for ingestion by CI systems like GitLab or Jenkins
+///
+/// ```
+/// use spekt::{report::JUnit, TestOutcome, TestReport};
+/// use std::time::Duration;
+///
+/// let mut junit = JUnit::new("database");
+///
+/// junit.add_case(
+///     "inserts_rows",
+///     &TestReport::<String> {
+///         name: None,
+///         before_duration: Duration::from_millis(10),
+///         test_duration: Duration::from_millis(20),
+///         after_duration: Duration::from_millis(5),
+///         steps: Vec::new(),
+///         assertion_count: 0,
+///         outcome: TestOutcome::Passed,
+///     },
+/// );
+/// junit.add_case(
+///     "rejects_duplicates",
+///     &TestReport {
+///         name: None,
+///         before_duration: Duration::from_millis(10),
+///         test_duration: Duration::from_millis(1),
+///         after_duration: Duration::from_millis(5),
+///         steps: Vec::new(),
+///         assertion_count: 0,
+///         outcome: TestOutcome::Failed("expected <unique> violation".to_string()),
+///     },
+/// );
+///
+/// let mut xml = Vec::new();
+///
+/// junit.write(&mut xml)?;
+///
+/// assert_eq!(
+///     String::from_utf8(xml).unwrap(),
+///     r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <testsuite name="database" tests="2" failures="1" skipped="0" time="0.051">
+///   <testcase name="inserts_rows" time="0.035"/>
+///   <testcase name="rejects_duplicates" time="0.016">
+///     <failure message="expected &lt;unique&gt; violation">expected &lt;unique&gt; violation</failure>
+///   </testcase>
+/// </testsuite>
+/// "#
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct JUnit {
+    name: String,
+    cases: Vec<Case>,
+}
+
+impl JUnit {
+    /// Create an empty report for a named suite
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            cases: Vec::new(),
+        }
+    }
+
+    /// Record the report of a single named run
+    pub fn add_case<E: fmt::Display>(&mut self, name: impl Into<String>, report: &TestReport<E>) {
+        let status = match (&report.outcome, failure_message(&report.outcome)) {
+            (TestOutcome::Skipped, _) | (TestOutcome::Flaky(_), _) => Status::Skipped,
+            (_, Some(message)) => Status::Failed(message),
+            (_, None) => Status::Passed,
+        };
+
+        self.cases.push(Case {
+            name: name.into(),
+            duration: report.total_duration(),
+            status,
+        });
+    }
+
+    /// Serialize the report as a JUnit XML `<testsuite>`
+    pub fn write(&self, mut writer: impl io::Write) -> io::Result<()> {
+        let failures = self
+            .cases
+            .iter()
+            .filter(|case| matches!(case.status, Status::Failed(_)))
+            .count();
+        let skipped = self
+            .cases
+            .iter()
+            .filter(|case| matches!(case.status, Status::Skipped))
+            .count();
+        let duration: Duration = self.cases.iter().map(|case| case.duration).sum();
+
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<testsuite name="{}" tests="{}" failures="{}" skipped="{}" time="{:.3}">"#,
+            escape(&self.name),
+            self.cases.len(),
+            failures,
+            skipped,
+            duration.as_secs_f64()
+        )?;
+
+        for case in &self.cases {
+            let name = escape(&case.name);
+            let time = case.duration.as_secs_f64();
+
+            match &case.status {
+                Status::Passed => writeln!(
+                    writer,
+                    r#"  <testcase name="{}" time="{:.3}"/>"#,
+                    name, time
+                )?,
+                Status::Failed(message) => {
+                    let message = escape(message);
+
+                    writeln!(writer, r#"  <testcase name="{}" time="{:.3}">"#, name, time)?;
+                    writeln!(
+                        writer,
+                        r#"    <failure message="{}">{}</failure>"#,
+                        message, message
+                    )?;
+                    writeln!(writer, "  </testcase>")?;
+                }
+                Status::Skipped => {
+                    writeln!(writer, r#"  <testcase name="{}" time="{:.3}">"#, name, time)?;
+                    writeln!(writer, "    <skipped/>")?;
+                    writeln!(writer, "  </testcase>")?;
+                }
+            }
+        }
+
+        writeln!(writer, "</testsuite>")
+    }
+}
+
+/// Escape text for use in XML attributes and content
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            character => escaped.push(character),
+        }
+    }
+
+    escaped
+}
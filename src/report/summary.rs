@@ -0,0 +1,124 @@
+use super::failure_message;
+use crate::outcome::{TestOutcome, TestReport};
+use std::{fmt, time::Duration};
+
+/// Totals across the named outcomes of a suite, for `fn main()`-style harnesses that exit with its status
+///
+/// ```
+/// use spekt::{report::Summary, TestOutcome};
+/// use std::time::Duration;
+///
+/// let mut summary = Summary::new();
+///
+/// summary.add("connects", &TestOutcome::<String>::Passed);
+/// summary.add("migrates", &TestOutcome::Failed("missing table".to_string()));
+/// summary.add("replicates", &TestOutcome::<String>::Skipped);
+/// summary.add("reconnects", &TestOutcome::Flaky(vec!["reset".to_string()]));
+/// summary.add("queries", &TestOutcome::<String>::TimedOut(Duration::from_secs(5)));
+///
+/// assert_eq!(summary.passed(), 1);
+/// assert_eq!(summary.failed(), 2);
+/// assert_eq!(summary.skipped(), 1);
+/// assert_eq!(summary.flaky(), 1);
+/// assert_eq!(summary.exit_code(), 1);
+/// assert_eq!(
+///     summary.to_string(),
+///     "test result: FAILED. 1 passed; 2 failed; 1 skipped; 1 flaky; finished in 0.00s"
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Summary {
+    passed: usize,
+    skipped: usize,
+    flaky: usize,
+    failures: Vec<(String, String)>,
+    duration: Duration,
+}
+
+impl Summary {
+    /// Create an empty summary
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the outcome of a single named test
+    pub fn add<E: fmt::Display>(&mut self, name: impl Into<String>, outcome: &TestOutcome<E>) {
+        match (outcome, failure_message(outcome)) {
+            (TestOutcome::Skipped, _) => self.skipped += 1,
+            (TestOutcome::Flaky(_), _) => self.flaky += 1,
+            (_, Some(message)) => self.failures.push((name.into(), message)),
+            (_, None) => self.passed += 1,
+        }
+    }
+
+    /// Record the outcome of a single named test along with the time spent across its phases
+    pub fn add_report<E: fmt::Display>(&mut self, name: impl Into<String>, report: &TestReport<E>) {
+        self.duration += report.total_duration();
+        self.add(name, &report.outcome);
+    }
+
+    /// Number of tests that passed
+    pub fn passed(&self) -> usize {
+        self.passed
+    }
+
+    /// Number of tests that failed, timed out, or panicked
+    pub fn failed(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Number of tests that were skipped
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Number of flaky tests that failed every attempt without failing the suite
+    pub fn flaky(&self) -> usize {
+        self.flaky
+    }
+
+    /// Number of tests recorded
+    pub fn total(&self) -> usize {
+        self.passed + self.failed() + self.skipped + self.flaky
+    }
+
+    /// Time spent across every test recorded with add_report
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Name and failure message of every failed test, in the order they were recorded
+    pub fn failures(&self) -> &[(String, String)] {
+        &self.failures
+    }
+
+    /// Process exit code for the suite: 1 if any test failed, 0 otherwise
+    pub fn exit_code(&self) -> i32 {
+        if self.failures.is_empty() {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.failures.is_empty() {
+            "ok"
+        } else {
+            "FAILED"
+        };
+
+        write!(
+            formatter,
+            "test result: {}. {} passed; {} failed; {} skipped; {} flaky; finished in {:.2}s",
+            status,
+            self.passed,
+            self.failed(),
+            self.skipped,
+            self.flaky,
+            self.duration.as_secs_f64()
+        )
+    }
+}
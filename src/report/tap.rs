@@ -0,0 +1,109 @@
+use super::failure_message;
+use crate::outcome::TestOutcome;
+use std::{fmt, io};
+
+/// Result of a single point in a TAP stream
+#[derive(Clone, Debug)]
+enum Status {
+    Ok,
+    NotOk(String),
+    Skipped,
+}
+
+/// Test Anything Protocol (version 13) stream of named test outcomes, for runners built on `Test::run`
+///
+/// ```
+/// use spekt::{report::Tap, TestOutcome};
+///
+/// let mut tap = Tap::new();
+///
+/// tap.add("connects", &TestOutcome::<String>::Passed);
+/// tap.add("migrates", &TestOutcome::Failed("missing \"users\" table".to_string()));
+/// tap.add("replicates", &TestOutcome::<String>::Skipped);
+///
+/// let mut stream = Vec::new();
+///
+/// tap.write(&mut stream)?;
+///
+/// assert_eq!(
+///     String::from_utf8(stream).unwrap(),
+///     r#"TAP version 13
+/// 1..3
+/// ok 1 - connects
+/// not ok 2 - migrates
+///   ---
+///   message: "missing \"users\" table"
+///   severity: fail
+///   ...
+/// ok 3 - replicates # SKIP
+/// "#
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Tap {
+    points: Vec<(String, Status)>,
+}
+
+impl Tap {
+    /// Create an empty stream
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the outcome of a single named test
+    pub fn add<E: fmt::Display>(&mut self, name: impl Into<String>, outcome: &TestOutcome<E>) {
+        let status = match (outcome, failure_message(outcome)) {
+            (TestOutcome::Skipped, _) | (TestOutcome::Flaky(_), _) => Status::Skipped,
+            (_, Some(message)) => Status::NotOk(message),
+            (_, None) => Status::Ok,
+        };
+
+        self.points.push((name.into(), status));
+    }
+
+    /// Serialize every recorded outcome as a TAP stream, with a YAML diagnostic block for each failure
+    pub fn write(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writeln!(writer, "TAP version 13")?;
+        writeln!(writer, "1..{}", self.points.len())?;
+
+        for (index, (name, status)) in self.points.iter().enumerate() {
+            let number = index + 1;
+            let name = name.replace('#', "\\#");
+
+            match status {
+                Status::Ok => writeln!(writer, "ok {} - {}", number, name)?,
+                Status::Skipped => writeln!(writer, "ok {} - {} # SKIP", number, name)?,
+                Status::NotOk(message) => {
+                    writeln!(writer, "not ok {} - {}", number, name)?;
+                    writeln!(writer, "  ---")?;
+                    writeln!(writer, "  message: {}", quote(message))?;
+                    writeln!(writer, "  severity: fail")?;
+                    writeln!(writer, "  ...")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Quote text as a double-quoted YAML scalar
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+
+    quoted.push('"');
+
+    for character in text.chars() {
+        match character {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            character => quoted.push(character),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,157 @@
+use crate::{context::Context, test::Test};
+use std::{
+    any::{self, Any, TypeId},
+    collections::HashMap,
+    error::Error,
+    fmt,
+    sync::Arc,
+};
+
+/// Typed registry of resources built during setup, keyed by their type, so that tasks can fetch what they need
+/// with `Context::resource` without depending on the shape of the fixture that built it.
+/// Filled by `Test::before_with_resources`
+///
+/// ```
+/// use spekt::{CleanupStack, Resources, Test};
+///
+/// struct DbPool(&'static str);
+/// struct Broker(u16);
+///
+/// struct Fixture;
+///
+/// #[spekt::async_trait]
+/// impl Test for Fixture {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+///
+///     async fn before_with_resources(
+///         _cleanup: &mut CleanupStack<Self::Error>,
+///         resources: &mut Resources,
+///     ) -> Result<Self, Self::Error> {
+///         resources.insert(DbPool("postgres://localhost"));
+///
+///         Ok(Self)
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// Fixture::test_with_context(|context| async move {
+///     context.insert(Broker(9092));
+///
+///     assert_eq!(context.resource::<DbPool>()?.0, "postgres://localhost");
+///     assert_eq!(context.resource::<Broker>()?.0, 9092);
+///
+///     let missing = context.resource::<String>().unwrap_err();
+///     assert_eq!(missing.to_string(), "no resource of type alloc::string::String was inserted");
+///
+///     Ok(())
+/// })
+/// .await;
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Resources {
+    entries: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Resources {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a resource, replacing any earlier resource of the same type
+    pub fn insert<R>(&mut self, resource: R)
+    where
+        R: Any + Send + Sync,
+    {
+        self.entries.insert(TypeId::of::<R>(), Arc::new(resource));
+    }
+
+    /// Get a shared handle to the resource of a type, if one was inserted
+    pub fn get<R>(&self) -> Option<Arc<R>>
+    where
+        R: Any + Send + Sync,
+    {
+        self.entries
+            .get(&TypeId::of::<R>())
+            .map(|resource| Arc::clone(resource).downcast().ok())?
+    }
+
+    /// Move every resource from another registry into this one, replacing resources of the same type
+    pub fn extend(&mut self, other: Self) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Number of inserted resources
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if any resources have been inserted
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Debug for Resources {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Resources")
+            .field("entries", &self.entries.len())
+            .finish()
+    }
+}
+
+/// Error fetching a resource of a type that was never inserted
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingResource {
+    type_name: &'static str,
+}
+
+impl MissingResource {
+    /// Name of the missing resource's type
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl fmt::Display for MissingResource {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "no resource of type {} was inserted",
+            self.type_name
+        )
+    }
+}
+
+impl Error for MissingResource {}
+
+impl<S: Test> Context<S> {
+    /// Insert a resource for the rest of the task, replacing any earlier resource of the same type
+    pub fn insert<R>(&self, resource: R)
+    where
+        R: Any + Send + Sync,
+    {
+        self.resources().lock().unwrap().insert(resource);
+    }
+
+    /// Fetch the resource of a type inserted during setup or earlier in the task
+    pub fn resource<R>(&self) -> Result<Arc<R>, MissingResource>
+    where
+        R: Any + Send + Sync,
+    {
+        self.resources()
+            .lock()
+            .unwrap()
+            .get()
+            .ok_or(MissingResource {
+                type_name: any::type_name::<R>(),
+            })
+    }
+}
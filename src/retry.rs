@@ -0,0 +1,115 @@
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// Strategy for spacing out repeated attempts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backoff {
+    /// Wait the same delay between every attempt
+    Fixed(Duration),
+    /// Grow the delay by the same step after every attempt
+    Linear(Duration),
+    /// Double the delay after every attempt, optionally randomizing up to half of each delay
+    Exponential {
+        /// Delay before the first retry
+        initial: Duration,
+        /// Randomize delays to keep concurrent retries from synchronizing
+        jitter: bool,
+    },
+}
+
+impl Backoff {
+    /// Delay before the given retry, counting from zero
+    pub fn delay(&self, retry: u32) -> Duration {
+        match *self {
+            Self::Fixed(delay) => delay,
+            Self::Linear(step) => step.saturating_mul(retry.saturating_add(1)),
+            Self::Exponential { initial, jitter } => {
+                let delay = 2u32
+                    .checked_pow(retry)
+                    .and_then(|factor| initial.checked_mul(factor))
+                    .unwrap_or(Duration::MAX);
+
+                if jitter {
+                    let half = delay / 2;
+                    let random = RandomState::new().build_hasher().finish();
+                    let nanos = half.as_nanos().min(u64::MAX as u128) as u64;
+
+                    half + Duration::from_nanos(random % nanos.saturating_add(1))
+                } else {
+                    delay
+                }
+            }
+        }
+    }
+}
+
+/// Policy describing how many times, and how patiently, to retry a test
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first
+    pub attempts: usize,
+    /// Delay strategy between attempts
+    pub backoff: Backoff,
+    /// Stop retrying once the next attempt would start after this much time has elapsed
+    pub max_elapsed: Option<Duration>,
+}
+
+impl RetryPolicy {
+    /// Retry with the same delay between every attempt
+    pub fn fixed(attempts: usize, delay: Duration) -> Self {
+        Self::new(attempts, Backoff::Fixed(delay))
+    }
+
+    /// Retry with a delay that grows by the same step after every attempt
+    pub fn linear(attempts: usize, step: Duration) -> Self {
+        Self::new(attempts, Backoff::Linear(step))
+    }
+
+    /// Retry with a jittered delay that doubles after every attempt
+    pub fn exponential(attempts: usize, initial: Duration) -> Self {
+        Self::new(
+            attempts,
+            Backoff::Exponential {
+                initial,
+                jitter: true,
+            },
+        )
+    }
+
+    /// Limit the total time spent across every attempt and delay
+    pub fn with_max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    fn new(attempts: usize, backoff: Backoff) -> Self {
+        Self {
+            attempts,
+            backoff,
+            max_elapsed: None,
+        }
+    }
+}
+
+/// Retry a single async operation up to `attempts` times, returning the first success or the last error
+pub async fn retry_op<F, Fut, T, E>(attempts: usize, mut op: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut remaining = attempts.max(1);
+
+    loop {
+        remaining -= 1;
+
+        match op().await {
+            Err(error) if remaining == 0 => return Err(error),
+            Err(_) => continue,
+            Ok(value) => return Ok(value),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::test::Test;
+use async_trait::async_trait;
+use std::{fmt, ops::Deref};
+
+/// Client of a resource that can scope its changes to a transaction, like a database connection
+#[async_trait]
+pub trait Transactional
+where
+    Self: Sized + Send + Sync,
+{
+    /// The format-able error shared by each step
+    type Error: fmt::Display + Send + Sync;
+
+    /// Connect a new client
+    async fn connect() -> Result<Self, Self::Error>;
+
+    /// Begin a transaction that scopes every change made through the client
+    async fn begin(&self) -> Result<(), Self::Error>;
+
+    /// Roll back the open transaction, discarding every change made since `begin`
+    async fn rollback(&self) -> Result<(), Self::Error>;
+}
+
+/// Test state wrapping a client in a transaction that is always rolled back after the test,
+/// regardless of its outcome
+///
+/// ```
+/// use spekt::{Rollback, Test, Transactional};
+/// use std::sync::Mutex;
+///
+/// #[derive(Default)]
+/// struct Store {
+///     committed: Mutex<Vec<String>>,
+///     pending: Mutex<Option<Vec<String>>>,
+/// }
+///
+/// impl Store {
+///     fn insert(&self, row: &str) {
+///         match &mut *self.pending.lock().unwrap() {
+///             Some(pending) => pending.push(row.into()),
+///             None => self.committed.lock().unwrap().push(row.into()),
+///         }
+///     }
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Transactional for Store {
+///     type Error = anyhow::Error;
+///
+///     async fn connect() -> Result<Self, Self::Error> {
+///         Ok(Self::default())
+///     }
+///
+///     async fn begin(&self) -> Result<(), Self::Error> {
+///         *self.pending.lock().unwrap() = Some(Vec::new());
+///
+///         Ok(())
+///     }
+///
+///     async fn rollback(&self) -> Result<(), Self::Error> {
+///         *self.pending.lock().unwrap() = None;
+///
+///         Ok(())
+///     }
+/// }
+///
+/// Rollback::<Store>::test_blocking(|store| async move {
+///     store.insert("row");
+///
+///     assert!(store.committed.lock().unwrap().is_empty());
+///
+///     Ok(())
+/// });
+/// ```
+pub struct Rollback<C> {
+    client: C,
+}
+
+impl<C> Rollback<C> {
+    /// The client running the transaction
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+}
+
+impl<C> Deref for Rollback<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for Rollback<C> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Rollback")
+            .field("client", &self.client)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<C: Transactional> Test for Rollback<C> {
+    type Error = C::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        let client = C::connect().await?;
+
+        client.begin().await?;
+
+        Ok(Self { client })
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        self.client.rollback().await
+    }
+}
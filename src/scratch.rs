@@ -0,0 +1,57 @@
+use crate::{context::Context, test::Test};
+use std::{io, path::PathBuf};
+pub use tempfile::TempDir;
+
+/// Create a uniquely-named temporary directory, removed along with its contents when dropped
+pub fn scratch_dir() -> io::Result<TempDir> {
+    tempfile::Builder::new().prefix("spekt-").tempdir()
+}
+
+impl<S: Test> Context<S> {
+    /// Create a uniquely-named temporary directory that is removed once the task finishes,
+    /// alongside other deferred clean-up and before after()
+    ///
+    /// ```
+    /// use spekt::Test;
+    ///
+    /// struct Fixture;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Fixture {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let scratch = std::sync::Arc::new(std::sync::Mutex::new(None));
+    /// let path = std::sync::Arc::clone(&scratch);
+    ///
+    /// Fixture::test_with_context(|context| async move {
+    ///     let directory = context.scratch_dir()?;
+    ///
+    ///     std::fs::write(directory.join("output.txt"), "hello")?;
+    ///     *path.lock().unwrap() = Some(directory);
+    ///
+    ///     Ok(())
+    /// })
+    /// .await;
+    ///
+    /// assert!(!scratch.lock().unwrap().take().unwrap().exists());
+    /// # }
+    /// ```
+    pub fn scratch_dir(&self) -> Result<PathBuf, S::Error>
+    where
+        S::Error: From<io::Error>,
+    {
+        let directory = scratch_dir()?;
+        let path = directory.path().to_path_buf();
+
+        self.defer(move || async move { Ok(directory.close()?) });
+
+        Ok(path)
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{context::Context, test::Test};
+use std::env;
+
+/// FNV-1a offset basis and prime, chosen so that seeds stay stable across Rust versions and platforms
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Hash bytes into a running FNV-1a hash
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+impl<S: Test> Context<S> {
+    /// Deterministic seed for the current run, derived from the test's name and attempt, so that a failure
+    /// involving randomness reproduces by rerunning the same named test. Setting `SPEKT_SEED` overrides the seed
+    /// of every run. Panics if `SPEKT_SEED` isn't a number
+    ///
+    /// ```
+    /// use spekt::{Test, TestConfig};
+    /// use std::sync::Mutex;
+    ///
+    /// static SEEDS: Mutex<Vec<u64>> = Mutex::new(Vec::new());
+    ///
+    /// struct Fixture;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Fixture {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// for name in ["shuffles_deck", "shuffles_deck", "deals_hand"] {
+    ///     Fixture::test_with_metadata(name, TestConfig::default(), |context| async move {
+    ///         SEEDS.lock().unwrap().push(context.seed());
+    ///
+    ///         Ok(())
+    ///     })
+    ///     .await;
+    /// }
+    ///
+    /// let seeds = SEEDS.lock().unwrap();
+    ///
+    /// assert_eq!(seeds[0], seeds[1]);
+    /// assert_ne!(seeds[0], seeds[2]);
+    /// # }
+    /// ```
+    pub fn seed(&self) -> u64 {
+        if let Some(seed) = env::var_os("SPEKT_SEED") {
+            let seed = seed.to_string_lossy();
+
+            return seed
+                .parse()
+                .unwrap_or_else(|_| panic!("invalid SPEKT_SEED {:?}, expected a number", seed));
+        }
+
+        let metadata = self.metadata();
+        let name = metadata.name.as_deref().unwrap_or_default();
+        let hash = fnv1a(FNV_OFFSET, name.as_bytes());
+
+        fnv1a(hash, &(metadata.attempt as u64).to_le_bytes())
+    }
+
+    /// Random number generator seeded with `Context::seed`, producing the same values whenever the seed repeats
+    #[cfg(feature = "rand")]
+    pub fn rng(&self) -> rand::rngs::StdRng {
+        rand::SeedableRng::seed_from_u64(self.seed())
+    }
+}
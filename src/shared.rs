@@ -0,0 +1,171 @@
+use crate::{gate::Gate, test::Test};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Process-wide fixture for expensive one-time setup (like starting a container), built by the first test that
+/// acquires it and reused by every test that acquires it while it's alive. Its after() only runs once the last
+/// test holding it releases it.
+///
+/// This trades isolation for speed: tests see each other's changes to the fixture, and a fixture released by
+/// every test is torn down, then built again by the next test to acquire it (e.g. when tests run one at a time).
+/// Fixtures bound to an async runtime (like most client connections) are also unusable from tests running on
+/// other runtimes, as `#[tokio::test]` tests each do.
+///
+/// ```
+/// use spekt::{SharedFixture, Test};
+/// use std::{
+///     sync::{
+///         atomic::{AtomicUsize, Ordering},
+///         Arc,
+///     },
+///     time::Duration,
+/// };
+///
+/// static BUILDS: AtomicUsize = AtomicUsize::new(0);
+/// static TEARDOWNS: AtomicUsize = AtomicUsize::new(0);
+/// static CONTAINER: SharedFixture<Container> = SharedFixture::new();
+///
+/// struct Container;
+///
+/// #[spekt::async_trait]
+/// impl Test for Container {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         BUILDS.fetch_add(1, Ordering::SeqCst);
+///
+///         Ok(Self)
+///     }
+///
+///     async fn after(&self) -> Result<(), Self::Error> {
+///         TEARDOWNS.fetch_add(1, Ordering::SeqCst);
+///
+///         Ok(())
+///     }
+/// }
+///
+/// struct Suite {
+///     container: Arc<Container>,
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Test for Suite {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         let container = CONTAINER.acquire().await?;
+///
+///         Ok(Self { container })
+///     }
+///
+///     async fn after(&self) -> Result<(), Self::Error> {
+///         CONTAINER.release().await
+///     }
+/// }
+///
+/// async fn uses_container() {
+///     Suite::test(|suite| async move {
+///         let _container = &suite.container;
+///         tokio::time::sleep(Duration::from_millis(10)).await;
+///
+///         Ok(())
+///     })
+///     .await
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// tokio::join!(uses_container(), uses_container(), uses_container());
+///
+/// assert_eq!(BUILDS.load(Ordering::SeqCst), 1);
+/// assert_eq!(TEARDOWNS.load(Ordering::SeqCst), 1);
+/// # }
+/// ```
+pub struct SharedFixture<T> {
+    gate: Gate,
+    shared: Mutex<Option<(Arc<T>, usize)>>,
+}
+
+impl<T: Test> SharedFixture<T> {
+    /// Create a fixture that hasn't been built yet
+    pub const fn new() -> Self {
+        Self {
+            gate: Gate::new(1),
+            shared: Mutex::new(None),
+        }
+    }
+
+    /// Share the fixture with one more test, building it with before() if no test holds it
+    pub async fn acquire(&self) -> Result<Arc<T>, T::Error> {
+        let _permit = self.gate.acquire().await;
+
+        if let Some((fixture, users)) = &mut *self.shared.lock().unwrap() {
+            *users += 1;
+
+            return Ok(Arc::clone(fixture));
+        }
+
+        let fixture = Arc::new(T::before().await?);
+
+        *self.shared.lock().unwrap() = Some((Arc::clone(&fixture), 1));
+
+        Ok(fixture)
+    }
+
+    /// Stop sharing the fixture with one test, tearing it down with after() if no other test holds it
+    pub async fn release(&self) -> Result<(), T::Error> {
+        let _permit = self.gate.acquire().await;
+
+        let fixture = {
+            let mut shared = self.shared.lock().unwrap();
+
+            match &mut *shared {
+                None => return Ok(()),
+                Some((_, users)) if *users > 1 => {
+                    *users -= 1;
+
+                    return Ok(());
+                }
+                Some(_) => shared.take().map(|(fixture, _)| fixture),
+            }
+        };
+
+        match fixture {
+            None => Ok(()),
+            Some(fixture) => fixture.after().await,
+        }
+    }
+
+    /// Number of tests currently holding the fixture
+    pub fn users(&self) -> usize {
+        self.shared
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(0, |(_, users)| *users)
+    }
+}
+
+impl<T: Test> Default for SharedFixture<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for SharedFixture<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let users = self
+            .shared
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(0, |(_, users)| *users);
+
+        formatter
+            .debug_struct("SharedFixture")
+            .field("users", &users)
+            .finish()
+    }
+}
@@ -0,0 +1,94 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Accumulator of non-fatal assertion failures, reported together once a task finishes
+#[derive(Clone, Debug, Default)]
+pub struct SoftAssertions {
+    failures: Arc<Mutex<Vec<String>>>,
+}
+
+impl SoftAssertions {
+    /// Create an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a failed result without aborting the task, returning the value of a successful one
+    pub fn check<T, E: fmt::Display>(&self, result: Result<T, E>) -> Option<T> {
+        crate::assert::__record_assertion();
+
+        match result {
+            Err(error) => {
+                self.fail(error.to_string());
+                None
+            }
+            Ok(value) => Some(value),
+        }
+    }
+
+    /// Record a failure if both values are not equal, returning whether they were
+    pub fn check_eq<T: PartialEq + fmt::Debug>(&self, left: T, right: T) -> bool {
+        crate::assert::__record_assertion();
+
+        let equal = left == right;
+
+        if !equal {
+            self.fail(format!(
+                "assertion `left == right` failed: {:?} != {:?}",
+                left, right
+            ));
+        }
+
+        equal
+    }
+
+    /// Record a failure message directly
+    pub fn fail(&self, message: impl Into<String>) {
+        self.failures.lock().unwrap().push(message.into());
+    }
+
+    /// Every failure recorded so far
+    pub fn failures(&self) -> Vec<String> {
+        self.failures.lock().unwrap().clone()
+    }
+
+    /// Combine every recorded failure into a single error, if any were recorded
+    pub fn finish(&self) -> Result<(), SoftFailures> {
+        let failures = self.failures();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(SoftFailures { failures })
+        }
+    }
+}
+
+/// Error listing every failure recorded by a set of soft assertions
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoftFailures {
+    failures: Vec<String>,
+}
+
+impl SoftFailures {
+    /// Every recorded failure message, in order
+    pub fn failures(&self) -> &[String] {
+        &self.failures
+    }
+}
+
+impl fmt::Display for SoftFailures {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{} soft assertions failed:", self.failures.len())?;
+
+        for failure in &self.failures {
+            write!(formatter, "\n  - {}", failure)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for SoftFailures {}
@@ -0,0 +1,191 @@
+use crate::{test::BoxFuture, unwind::catch_unwind};
+use std::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// Runtime-agnostic handle for spawning futures onto an executor, enabling truly concurrent test runners
+/// like `Test::test_parallel_spawned` without tying the crate to one runtime
+pub trait Spawner: Send + Sync {
+    /// Spawn a future to run in the background until it completes
+    fn spawn(&self, future: BoxFuture<'static, ()>);
+}
+
+/// Spawner for the current tokio runtime
+#[cfg(feature = "tokio")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioSpawner;
+
+#[cfg(feature = "tokio")]
+impl Spawner for TokioSpawner {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        tokio::spawn(future);
+    }
+}
+
+/// Spawner for async-std's global executor
+#[cfg(feature = "async-std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsyncStdSpawner;
+
+#[cfg(feature = "async-std")]
+impl Spawner for AsyncStdSpawner {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        async_std::task::spawn(future);
+    }
+}
+
+type Output<T> = Result<T, Box<dyn Any + Send>>;
+
+/// Output of a spawned future, along with the waker of the task awaiting it
+struct Slot<T> {
+    output: Option<Output<T>>,
+    dropped: bool,
+    waker: Option<Waker>,
+}
+
+/// Sending half of a spawned future's output, marking the output as lost if dropped before completing
+struct Completion<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> Completion<T> {
+    fn complete(self, output: Output<T>) {
+        self.slot.lock().unwrap().output = Some(output);
+    }
+}
+
+impl<T> Drop for Completion<T> {
+    fn drop(&mut self) {
+        let mut slot = self.slot.lock().unwrap();
+
+        slot.dropped = true;
+
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Future resolving to the output of a spawned future, or its panic payload
+pub(crate) struct Spawned<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> Future for Spawned<T> {
+    type Output = Output<T>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().unwrap();
+
+        if let Some(output) = slot.output.take() {
+            return Poll::Ready(output);
+        }
+
+        if slot.dropped {
+            return Poll::Ready(Err(Box::new(
+                "spawned future was dropped before completing",
+            )));
+        }
+
+        slot.waker = Some(context.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+/// Spawn a future through the spawner, returning a future that resolves to its output
+pub(crate) fn spawn<F>(spawner: &dyn Spawner, future: F) -> Spawned<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let slot = Arc::new(Mutex::new(Slot {
+        output: None,
+        dropped: false,
+        waker: None,
+    }));
+    let completion = Completion {
+        slot: Arc::clone(&slot),
+    };
+
+    spawner.spawn(Box::pin(async move {
+        completion.complete(catch_unwind(future).await);
+    }));
+
+    Spawned { slot }
+}
+
+/// Spawner for the runtime driving the current task, if the crate knows how to spawn onto it: tokio's with the
+/// `tokio` feature inside a tokio runtime, or async-std's with the `async-std` feature
+pub(crate) fn current_spawner() -> Option<&'static dyn Spawner> {
+    #[cfg(feature = "tokio")]
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return Some(&TokioSpawner);
+    }
+
+    #[cfg(feature = "async-std")]
+    return Some(&AsyncStdSpawner);
+
+    #[cfg(not(feature = "async-std"))]
+    None
+}
+
+/// Run blocking work, like reading a large file or calling a synchronous driver, without stalling the executor.
+/// With the `tokio` feature inside a tokio runtime, or with the `async-std` feature, the closure is offloaded to
+/// the runtime's blocking thread pool; otherwise it runs inline. Panics in the closure are re-raised, and work
+/// that a shutting-down tokio runtime cancels before it runs panics, since there is no output to return
+///
+/// ```
+/// use spekt::Test;
+/// use std::{thread, time::Duration};
+///
+/// struct Snapshot {
+///     rows: Vec<String>,
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Test for Snapshot {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         let rows = spekt::spawn_blocking(|| {
+///             thread::sleep(Duration::from_millis(10));
+///
+///             vec!["row".to_string()]
+///         })
+///         .await;
+///
+///         Ok(Self { rows })
+///     }
+/// }
+///
+/// Snapshot::test_blocking(|snapshot| async move {
+///     assert_eq!(snapshot.rows, ["row"]);
+///
+///     Ok(())
+/// });
+/// ```
+pub async fn spawn_blocking<F, T>(work: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    #[cfg(feature = "tokio")]
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return match tokio::task::spawn_blocking(work).await {
+            Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+            Err(_) => panic!("blocking work was cancelled because the tokio runtime shut down"),
+            Ok(output) => output,
+        };
+    }
+
+    #[cfg(feature = "async-std")]
+    return async_std::task::spawn_blocking(work).await;
+
+    #[cfg(not(feature = "async-std"))]
+    work()
+}
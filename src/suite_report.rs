@@ -0,0 +1,125 @@
+use crate::{
+    config::TestConfig,
+    lifecycle::{self, AfterRunner},
+    test::{BoxFuture, Test},
+};
+use async_trait::async_trait;
+use std::{
+    future::Future,
+    mem,
+    sync::{Arc, Mutex},
+};
+
+/// Test state whose teardown reports a value, like the number of rows it deleted or the time it spent,
+/// collected across a suite and handed to a final aggregation once every test has run
+///
+/// ```
+/// use spekt::{SuiteReport, Test};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static DELETED: AtomicUsize = AtomicUsize::new(0);
+///
+/// struct Table {
+///     rows: usize,
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Test for Table {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self { rows: 2 })
+///     }
+/// }
+///
+/// #[spekt::async_trait]
+/// impl SuiteReport for Table {
+///     type AfterReport = usize;
+///
+///     async fn after_report(&self) -> Result<Self::AfterReport, Self::Error> {
+///         Ok(self.rows)
+///     }
+///
+///     async fn after_all_reports(reports: Vec<Self::AfterReport>) -> Result<(), Self::Error> {
+///         DELETED.store(reports.into_iter().sum(), Ordering::SeqCst);
+///
+///         Ok(())
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let task = |_| async { Ok(()) };
+///
+/// Table::suite_reported(vec![task, task, task]).await;
+///
+/// assert_eq!(DELETED.load(Ordering::SeqCst), 6);
+/// # }
+/// ```
+#[async_trait]
+pub trait SuiteReport: Test {
+    /// Value reported by the teardown of each test
+    type AfterReport: Send;
+
+    /// Clean up after a test run in place of after(), reporting a value for after_all_reports
+    async fn after_report(&self) -> Result<Self::AfterReport, Self::Error>;
+
+    /// Clean up shared resources once after a suite in place of after_all(), with the report of every
+    /// teardown that succeeded, in the order the tests ran
+    async fn after_all_reports(reports: Vec<Self::AfterReport>) -> Result<(), Self::Error>;
+
+    /// Run a series of test tasks, each with its own lifecycle, between before_all and after_all_reports,
+    /// collecting the report of each test's after_report
+    async fn suite_reported<I, F, T>(tasks: I)
+    where
+        I: IntoIterator<Item = T> + Send,
+        I::IntoIter: Send,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        let reports = Reports::<Self>::default();
+        let runner = AfterRunner::Collector(&reports);
+
+        lifecycle::suite::<Self, _, _, _>(&TestConfig::default(), Some(&runner), tasks).await
+    }
+}
+
+/// Teardown that runs in place of after(), keeping whatever it reports for a final aggregation
+pub(crate) trait Collector<S: Test>: Sync {
+    /// Tear down the state of a single test, keeping its report
+    fn after<'a>(&'a self, state: &'a S) -> BoxFuture<'a, Result<(), S::Error>>;
+
+    /// Hand every kept report to the final aggregation
+    fn after_all(&self) -> BoxFuture<'_, Result<(), S::Error>>;
+}
+
+/// Reports collected by `SuiteReport::suite_reported`
+struct Reports<S: SuiteReport> {
+    reports: Mutex<Vec<S::AfterReport>>,
+}
+
+impl<S: SuiteReport> Default for Reports<S> {
+    fn default() -> Self {
+        Self {
+            reports: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<S: SuiteReport> Collector<S> for Reports<S> {
+    fn after<'a>(&'a self, state: &'a S) -> BoxFuture<'a, Result<(), S::Error>> {
+        Box::pin(async move {
+            let report = state.after_report().await?;
+
+            self.reports.lock().unwrap().push(report);
+
+            Ok(())
+        })
+    }
+
+    fn after_all(&self) -> BoxFuture<'_, Result<(), S::Error>> {
+        let reports = mem::take(&mut *self.reports.lock().unwrap());
+
+        S::after_all_reports(reports)
+    }
+}
@@ -1,5 +1,40 @@
+use crate::{
+    assert::AssertionError,
+    bench::BenchStats,
+    cleanup::CleanupStack,
+    config::{FailureMode, Isolation, TestConfig},
+    context::Context,
+    detach::{Background, Detached},
+    executor::{block_on, EnterError},
+    fork::ForkUnsupported,
+    future::TestFuture,
+    join::join_bounded,
+    lifecycle::{self, AfterRunner, Failure},
+    metadata::Metadata,
+    outcome::{BeforeOutcome, TestOutcome, TestReport},
+    pretty,
+    resource::Resources,
+    retry::RetryPolicy,
+    soft::{SoftAssertions, SoftFailures},
+    spawn::{current_spawner, spawn, Spawner},
+    timer::sleep,
+    trace,
+    unwind::{catch_unwind, panic_message},
+};
 use async_trait::async_trait;
-use std::{future::Future, sync::Arc};
+use std::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// Boxed, sendable future borrowing from its environment for `'a`
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
 /// Test-running trait to handle test lifecycles
 #[async_trait]
@@ -10,36 +45,1614 @@ where
     /// The format-able error shared by each step. anyhow::Error is recommended!
     type Error: std::fmt::Display + Send + Sync;
 
+    /// Format an error for a failure message. Override to print source chains or alternate forms
+    fn fmt_failure(error: &Self::Error) -> String {
+        pretty::error(format!("{}", error))
+    }
+
+    /// Combine two errors raised while tearing down the same test, e.g. by composed fixtures.
+    /// Keeps the first error by default; override to aggregate both
+    fn combine_errors(first: Self::Error, _later: Self::Error) -> Self::Error {
+        first
+    }
+
+    /// Whether a flaky test that fails every attempt should fail the suite instead of only warning
+    fn fail_on_flaky() -> bool {
+        false
+    }
+
+    /// Optionally convert a panic caught in a test task into an error, instead of re-raising it after teardown.
+    /// `spekt::panic_message` extracts the message of `panic!`- and `assert!`-style payloads
+    fn panic_to_error(_payload: &(dyn Any + Send)) -> Option<Self::Error> {
+        None
+    }
+
+    /// Optionally convert the failure of a task that made fewer assertions than `TestConfig::min_assertions` into
+    /// an error, failing the test with it. Errors that convert from `AssertionError`, as spekt's assertion macros
+    /// already require, can return `Some(error.into())`. Without a conversion, the failure is re-raised as a panic
+    /// once the test has been torn down
+    ///
+    /// ```
+    /// use spekt::{AssertionError, Test, TestConfig, TestOutcome};
+    ///
+    /// struct Fixture;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Fixture {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    ///
+    ///     fn assertion_error(error: AssertionError) -> Option<Self::Error> {
+    ///         Some(error.into())
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let config = TestConfig::builder().min_assertions(2).build();
+    /// let outcome = Fixture::run_with_config(config, |_| async {
+    ///     spekt::ensure!(1 + 1 == 2);
+    ///
+    ///     Ok(())
+    /// })
+    /// .await;
+    ///
+    /// match outcome {
+    ///     TestOutcome::Failed(error) => {
+    ///         assert_eq!(error.to_string(), "expected at least 2 assertions, but the task made 1")
+    ///     }
+    ///     outcome => panic!("unexpected outcome {:?}", outcome),
+    /// }
+    /// # }
+    /// ```
+    fn assertion_error(_error: AssertionError) -> Option<Self::Error> {
+        None
+    }
+
+    /// Report a before() that took longer than `TestConfig::slow_before_threshold`.
+    /// Warns with the fixture's type and elapsed time by default, as a tracing event with the `tracing` feature
+    fn warn_slow_before(elapsed: Duration, threshold: Duration) {
+        trace::warn(&format!(
+            "before() of {} took {:?}, exceeding the {:?} threshold",
+            std::any::type_name::<Self>(),
+            elapsed,
+            threshold
+        ));
+    }
+
+    /// Optionally load configuration like environment variables before each `before`.
+    /// With the `dotenv` feature, the default loads a `.env` file from the working directory or its parents,
+    /// ignoring a missing or malformed file; override to surface those failures
+    async fn env() -> Result<(), Self::Error> {
+        #[cfg(feature = "dotenv")]
+        let _ = dotenvy::dotenv();
+
+        Ok(())
+    }
+
     /// Initialize test suite with new instance of test's state
     async fn before() -> Result<Self, Self::Error>;
 
+    /// Initialize test state, registering clean-up actions that run even if setup fails partway through
+    async fn before_with_cleanup(
+        _cleanup: &mut CleanupStack<Self::Error>,
+    ) -> Result<Self, Self::Error> {
+        Self::before().await
+    }
+
+    /// Initialize test state, registering clean-up actions and inserting resources that tasks can fetch by type
+    /// with `Context::resource`
+    async fn before_with_resources(
+        cleanup: &mut CleanupStack<Self::Error>,
+        _resources: &mut Resources,
+    ) -> Result<Self, Self::Error> {
+        Self::before_with_cleanup(cleanup).await
+    }
+
+    /// Initialize test state, or skip the test instead of failing it when prerequisites are missing.
+    /// Clean-up registered before skipping still runs, but neither the task nor after() does
+    async fn before_or_skip(
+        cleanup: &mut CleanupStack<Self::Error>,
+        resources: &mut Resources,
+    ) -> Result<BeforeOutcome<Self>, Self::Error> {
+        Self::before_with_resources(cleanup, resources)
+            .await
+            .map(BeforeOutcome::Ready)
+    }
+
+    /// Optionally shut resources down gracefully (e.g. draining a server or flushing a pool) once the task finishes,
+    /// before after() runs. after() still runs if shutdown fails, with both errors combined
+    ///
+    /// ```
+    /// use spekt::Test;
+    /// use std::sync::Mutex;
+    ///
+    /// static EVENTS: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+    ///
+    /// struct Server;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Server {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    ///
+    ///     async fn shutdown(&self) -> Result<(), Self::Error> {
+    ///         EVENTS.lock().unwrap().push("shutdown");
+    ///
+    ///         Ok(())
+    ///     }
+    ///
+    ///     async fn after(&self) -> Result<(), Self::Error> {
+    ///         EVENTS.lock().unwrap().push("after");
+    ///
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// Server::test_blocking(|_| async { Ok(()) });
+    ///
+    /// assert_eq!(*EVENTS.lock().unwrap(), ["shutdown", "after"]);
+    /// ```
+    async fn shutdown(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Optionally add diagnostics gathered from live resources to the error of a failed task (e.g. dumping
+    /// the current contents of a database), before shutdown() and after() run
+    ///
+    /// ```
+    /// use spekt::{Test, TestOutcome};
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// static AFTER_RAN: AtomicBool = AtomicBool::new(false);
+    ///
+    /// struct Database {
+    ///     rows: usize,
+    /// }
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Database {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self { rows: 3 })
+    ///     }
+    ///
+    ///     async fn enrich_error(&self, error: Self::Error) -> Self::Error {
+    ///         error.context(format!("database held {} rows", self.rows))
+    ///     }
+    ///
+    ///     async fn after(&self) -> Result<(), Self::Error> {
+    ///         AFTER_RAN.store(true, Ordering::SeqCst);
+    ///
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let report = Database::test_reported(|_| async { anyhow::bail!("missing user") }).await;
+    ///
+    /// match report.outcome {
+    ///     TestOutcome::Failed(error) => {
+    ///         assert_eq!(format!("{:#}", error), "database held 3 rows: missing user")
+    ///     }
+    ///     outcome => panic!("unexpected outcome {:?}", outcome),
+    /// }
+    ///
+    /// assert!(AFTER_RAN.load(Ordering::SeqCst));
+    /// # }
+    /// ```
+    async fn enrich_error(&self, error: Self::Error) -> Self::Error {
+        error
+    }
+
+    /// Optionally create an isolated child of this state, like a new schema copied from the parent's, for a
+    /// sub-scenario run through `Context::fork`. Fails with `ForkUnsupported` unless overridden
+    async fn fork(&self) -> Result<Self, Self::Error>
+    where
+        Self::Error: From<ForkUnsupported>,
+    {
+        Err(ForkUnsupported.into())
+    }
+
     /// Optionally clean up after test run
     async fn after(&self) -> Result<(), Self::Error> {
         Ok(())
     }
 
-    /// Run a Result-emitting test task, handling assertion errors gracefully
+    /// Optionally clean up after test run based on whether the task passed or failed
+    async fn after_outcome(&self, _outcome: &TestOutcome<Self::Error>) -> Result<(), Self::Error> {
+        self.after().await
+    }
+
+    /// Optionally clean up after test run using the task's output, if the task passed.
+    /// Overrides can recover a concrete output by downcasting through `std::any::Any`
+    async fn after_with<R>(
+        &self,
+        _output: Option<&R>,
+        outcome: &TestOutcome<Self::Error>,
+    ) -> Result<(), Self::Error>
+    where
+        R: Any + Send + Sync,
+    {
+        self.after_outcome(outcome).await
+    }
+
+    /// Optionally prepare state before each task run by `suite` or `test_shared`, without rebuilding it
+    async fn before_each(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Optionally reset state after each task run by `suite` or `test_shared`, even if the task fails
+    async fn after_each(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Optionally prepare shared resources once before a suite of tests
+    async fn before_all() -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Optionally clean up shared resources once after a suite of tests
+    async fn after_all() -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Optionally observe the start of a named test, like `test_named` or a `Workflow` case, before its
+    /// lifecycle begins. Override to push metrics or annotate traces in one place
+    ///
+    /// ```
+    /// use spekt::{Test, TestOutcome};
+    /// use std::sync::Mutex;
+    ///
+    /// static EVENTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    ///
+    /// struct Monitored;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Monitored {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    ///
+    ///     async fn on_start(name: &str) {
+    ///         EVENTS.lock().unwrap().push(format!("start {}", name));
+    ///     }
+    ///
+    ///     async fn on_finish(name: &str, outcome: &TestOutcome<Self::Error>) {
+    ///         EVENTS.lock().unwrap().push(format!("finish {} (passed: {})", name, outcome.is_passed()));
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// Monitored::test_named("creates_user", |_| async {
+    ///     EVENTS.lock().unwrap().push("task".to_string());
+    ///
+    ///     Ok(())
+    /// })
+    /// .await;
+    ///
+    /// assert_eq!(
+    ///     *EVENTS.lock().unwrap(),
+    ///     ["start creates_user", "task", "finish creates_user (passed: true)"]
+    /// );
+    /// # }
+    /// ```
+    async fn on_start(_name: &str) {}
+
+    /// Optionally observe the final outcome of a named test once its lifecycle, including after(), has finished
+    async fn on_finish(_name: &str, _outcome: &TestOutcome<Self::Error>) {}
+
+    /// Run a Result-emitting test task, returning the first error encountered
+    async fn try_test<F, T>(task: T) -> Result<(), Self::Error>
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        lifecycle::try_run::<Self, _, _>(task).await
+    }
+
+    /// Run a Result-emitting test task, reporting its outcome instead of panicking, even if the task panics
+    async fn run<F, T>(task: T) -> TestOutcome<Self::Error>
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        Self::run_with_config(TestConfig::default(), task).await
+    }
+
+    /// Run a Result-emitting test task with every option in the config, reporting its outcome instead of
+    /// panicking, even if the task panics
+    async fn run_with_config<F, T>(config: TestConfig, task: T) -> TestOutcome<Self::Error>
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        match catch_unwind(lifecycle::run::<Self, _, _, _>(&config, task)).await {
+            Err(payload) => TestOutcome::Panicked(panic_message(&*payload)),
+            Ok(run) => run.into_outcome(),
+        }
+    }
+
+    /// Build the lifecycle of a Result-emitting test task as a `TestFuture` resolving to its outcome,
+    /// for driving by hand with a custom executor or waker
+    fn test_future<'a, F, T>(task: T) -> TestFuture<'a, Self::Error>
+    where
+        Self: 'a,
+        F: Future<Output = Result<(), Self::Error>> + Send + 'a,
+        T: Send + Sync + 'a + FnOnce(Arc<Self>) -> F,
+    {
+        TestFuture::new(Self::run(task))
+    }
+
+    /// Run a Result-emitting test task, handling assertion errors gracefully.
+    /// A failure panics with the error formatted by `fmt_failure` as the panic's payload, so
+    /// `#[should_panic(expected = "...")]` tests can match on the error's own message:
+    ///
+    /// ```
+    /// use spekt::Test;
+    /// use std::panic;
+    ///
+    /// struct Database;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Database {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// // #[tokio::test]
+    /// // #[should_panic(expected = "connection refused")]
+    /// async fn rejects_unknown_hosts() {
+    ///     Database::test(|_| async { anyhow::bail!("connection refused") }).await
+    /// }
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    /// let payload = panic::catch_unwind(|| runtime.block_on(rejects_unknown_hosts())).unwrap_err();
+    ///
+    /// assert_eq!(payload.downcast_ref::<String>().unwrap(), "connection refused");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
     async fn test<F, T>(task: T)
     where
         F: Future<Output = Result<(), Self::Error>> + Send,
         T: Send + Sync + FnOnce(Arc<Self>) -> F,
     {
-        let before = Self::before().await;
+        match Self::run(task).await {
+            TestOutcome::Passed | TestOutcome::Skipped | TestOutcome::Flaky(_) => {}
+            TestOutcome::Failed(error) => panic!("{}", Self::fmt_failure(&error)),
+            TestOutcome::TimedOut(duration) => panic!("timed out after {:?}", duration),
+            TestOutcome::Panicked(message) => panic!("{}", message),
+        }
+    }
+
+    /// Run a Result-emitting test task under a name, attributing any failure to it as `test "name" failed: ...`
+    ///
+    /// ```should_panic
+    /// use spekt::Test;
+    ///
+    /// struct Fixture;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Fixture {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// Fixture::test_named("creates_user", |_| async move { Err(anyhow::anyhow!("duplicate user")) }).await;
+    /// # }
+    /// ```
+    async fn test_named<F, T>(name: &str, task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        Self::on_start(name).await;
+
+        let (outcome, timings) = match catch_unwind(lifecycle::run::<Self, _, _, _>(
+            &TestConfig::default(),
+            task,
+        ))
+        .await
+        {
+            Err(payload) => (TestOutcome::Panicked(panic_message(&*payload)), None),
+            Ok(run) => {
+                let timings = run.timings;
+
+                (run.into_outcome(), Some(timings))
+            }
+        };
+
+        Self::on_finish(name, &outcome).await;
 
-        let state = match before {
-            Err(error) => return assert!(false, format!("{}", error)),
-            Ok(state) => Arc::new(state),
+        let message = match outcome {
+            TestOutcome::Passed | TestOutcome::Skipped | TestOutcome::Flaky(_) => return,
+            TestOutcome::Failed(error) => Self::fmt_failure(&error),
+            TestOutcome::TimedOut(duration) => format!("timed out after {:?}", duration),
+            TestOutcome::Panicked(message) => format!("panicked: {}", message),
         };
 
-        let test_run = task(Arc::clone(&state)).await;
-        let after = state.after().await;
+        panic!(
+            "{}",
+            pretty::failure(&format!("test \"{}\" failed", name), &message, timings)
+        );
+    }
+
+    /// Run a Result-emitting test task while capturing everything written to stdout and stderr,
+    /// appending the captured output to the failure message if the task fails or panics.
+    /// Output is redirected at the file descriptor level, so libtest's own capturing must be off (`--nocapture`
+    /// or a `harness = false` target) for `print!` output to reach it
+    #[cfg(feature = "capture")]
+    async fn test_captured<F, T>(task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        let capture = crate::capture::Capture::start();
+        let result = catch_unwind(lifecycle::try_run::<Self, _, _>(task)).await;
+        let captured = capture.finish();
+
+        match result {
+            Err(payload) => panic!(
+                "{}",
+                captured.append_to(lifecycle::panic_failure::<Self>(&*payload))
+            ),
+            Ok(Err(error)) => panic!("{}", captured.append_to(Self::fmt_failure(&error))),
+            Ok(Ok(())) => captured.replay(),
+        }
+    }
+
+    /// Run a Result-emitting test task that is expected to fail or panic, documenting known-broken behavior.
+    /// The suite only fails if the task unexpectedly passes
+    async fn test_xfail<F, T>(task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        if Self::run(task).await.is_passed() {
+            panic!("expected failure but test passed");
+        }
+    }
+
+    /// Run a Result-emitting test task unless the predicate holds, returning a Skipped outcome without running
+    /// before(), the task or after() if it does, and the task's outcome otherwise, even if the task panics
+    async fn test_skip_if<P, F, T>(predicate: P, task: T) -> TestOutcome<Self::Error>
+    where
+        P: Send + FnOnce() -> bool,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        if predicate() {
+            return TestOutcome::Skipped;
+        }
+
+        Self::run(task).await
+    }
+
+    /// Run a Result-emitting test task with a context that can defer clean-up registered mid-test
+    async fn test_with_context<F, T>(task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Context<Self>) -> F,
+    {
+        let run = lifecycle::run_with_context::<Self, _, _, _>(&TestConfig::default(), task).await;
+
+        if let Err(message) = run.into_message_result::<Self>() {
+            panic!("{}", message);
+        }
+    }
+
+    /// Run a Result-emitting test task with a context, reporting its outcome, the time spent in each phase,
+    /// and every step recorded through `Context::step`
+    async fn test_reported_with_context<F, T>(task: T) -> TestReport<Self::Error>
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Context<Self>) -> F,
+    {
+        lifecycle::run_with_context::<Self, _, _, _>(&TestConfig::default(), task)
+            .await
+            .into_report()
+    }
+
+    /// Run a Result-emitting test task, reporting its outcome and the time spent in each phase.
+    /// A panicking task is re-raised once after() has run, like it is by `test`
+    async fn test_reported<F, T>(task: T) -> TestReport<Self::Error>
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        lifecycle::run::<Self, _, _, _>(&TestConfig::default(), task)
+            .await
+            .into_report()
+    }
+
+    /// Run a test task that produces an output, handing it to after_with before returning it,
+    /// or returning nothing if before skipped the test
+    async fn test_returning<R, F, T>(task: T) -> Option<R>
+    where
+        R: Any + Send + Sync,
+        F: Future<Output = Result<R, Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        match lifecycle::try_run_returning::<Self, _, _, _>(task).await {
+            Err(error) => panic!("{}", Self::fmt_failure(&error)),
+            Ok(output) => output,
+        }
+    }
+
+    /// Run a Result-emitting test task, handing the final state back once after has run, so that callers can
+    /// make further assertions on it. Returns nothing if before skipped the test, since there is no state to hand back
+    ///
+    /// ```
+    /// use spekt::Test;
+    /// use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    ///
+    /// #[derive(Default)]
+    /// struct Counter {
+    ///     count: AtomicUsize,
+    ///     closed: AtomicBool,
+    /// }
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Counter {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self::default())
+    ///     }
+    ///
+    ///     async fn after(&self) -> Result<(), Self::Error> {
+    ///         self.closed.store(true, Ordering::SeqCst);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let counter = Counter::test_into_state(|counter| async move {
+    ///     counter.count.fetch_add(2, Ordering::SeqCst);
+    ///     Ok(())
+    /// })
+    /// .await
+    /// .unwrap();
+    ///
+    /// assert_eq!(counter.count.load(Ordering::SeqCst), 2);
+    /// assert!(counter.closed.load(Ordering::SeqCst));
+    /// # }
+    /// ```
+    async fn test_into_state<F, T>(task: T) -> Option<Arc<Self>>
+    where
+        Self: 'static,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        match Self::try_test_into_state(task).await {
+            Err(error) => panic!("{}", Self::fmt_failure(&error)),
+            Ok(state) => state,
+        }
+    }
+
+    /// Run a Result-emitting test task, handing the final state back once after has run (or nothing if before
+    /// skipped the test), or returning the first error encountered
+    async fn try_test_into_state<F, T>(task: T) -> Result<Option<Arc<Self>>, Self::Error>
+    where
+        Self: 'static,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        lifecycle::try_run_returning::<Self, _, _, _>(|state: Arc<Self>| async move {
+            task(Arc::clone(&state)).await?;
+
+            Ok(state)
+        })
+        .await
+    }
+
+    /// Run a Result-emitting test task with soft assertions, failing with every recorded failure once it finishes
+    async fn test_soft<F, T>(task: T)
+    where
+        Self::Error: From<SoftFailures>,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>, SoftAssertions) -> F,
+    {
+        let test_run = lifecycle::try_run::<Self, _, _>(|state| async move {
+            let soft = SoftAssertions::new();
+
+            if let Err(error) = task(state, soft.clone()).await {
+                soft.fail(Self::fmt_failure(&error));
+            }
+
+            soft.finish().map_err(Self::Error::from)
+        })
+        .await;
 
         if let Err(error) = test_run {
-            assert!(false, format!("{}", error));
+            panic!("{}", Self::fmt_failure(&error));
+        }
+    }
+
+    /// Run a Result-emitting test task with exclusive, mutable access to the test's state
+    async fn test_mut<T>(task: T)
+    where
+        T: Send + for<'a> FnOnce(&'a mut Self) -> BoxFuture<'a, Result<(), Self::Error>>,
+    {
+        let run = lifecycle::run_mut::<Self, _, _>(&TestConfig::default(), task).await;
+
+        if let Err(failure) = run.into_result() {
+            panic!("{}", failure.message::<Self>());
+        }
+    }
+
+    /// Run a Result-emitting test task that borrows the test's state, which lives on the runner's stack
+    /// instead of behind an `Arc`. Saves an allocation per test for the common single-task case, and lets
+    /// the task hold borrows of the state's fields across await points
+    ///
+    /// ```
+    /// use spekt::Test;
+    ///
+    /// struct Catalog {
+    ///     products: Vec<String>,
+    /// }
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Catalog {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self {
+    ///             products: vec!["lamp".to_string(), "rug".to_string()],
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// Catalog::test_ref(|catalog| {
+    ///     Box::pin(async move {
+    ///         let products: &[String] = &catalog.products;
+    ///         tokio::task::yield_now().await;
+    ///
+    ///         anyhow::ensure!(products == ["lamp", "rug"], "unexpected products {:?}", products);
+    ///
+    ///         Ok(())
+    ///     })
+    /// })
+    /// .await;
+    /// # }
+    /// ```
+    async fn test_ref<T>(task: T)
+    where
+        T: Send + for<'a> FnOnce(&'a Self) -> BoxFuture<'a, Result<(), Self::Error>>,
+    {
+        let run = lifecycle::run_mut::<Self, _, _>(&TestConfig::default(), |state: &mut Self| {
+            task(state)
+        })
+        .await;
+
+        if let Err(failure) = run.into_result() {
+            panic!("{}", failure.message::<Self>());
+        }
+    }
+
+    /// Run a Result-emitting test task, failing if the task itself doesn't finish within the duration
+    async fn test_with_timeout<F, T>(duration: Duration, task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        let config = TestConfig {
+            test_timeout: Some(duration),
+            ..TestConfig::default()
+        };
+        let run = lifecycle::run::<Self, _, _, _>(&config, task).await;
+
+        if let Err(failure) = run.into_result() {
+            panic!("{}", failure.message::<Self>());
+        }
+    }
+
+    /// Run a Result-emitting test task with a context whose deadline reflects the timeout, failing if the task
+    /// doesn't finish within the duration
+    ///
+    /// ```
+    /// use spekt::Test;
+    /// use std::time::Duration;
+    ///
+    /// struct Fixture;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Fixture {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let total = Duration::from_secs(5);
+    ///
+    /// Fixture::test_with_deadline(total, move |context| async move {
+    ///     let deadline = context.deadline().expect("task runs with a timeout");
+    ///     assert!(deadline.remaining() < total);
+    ///
+    ///     Ok(())
+    /// })
+    /// .await;
+    /// # }
+    /// ```
+    async fn test_with_deadline<F, T>(duration: Duration, task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Context<Self>) -> F,
+    {
+        let config = TestConfig {
+            test_timeout: Some(duration),
+            ..TestConfig::default()
+        };
+        let run = lifecycle::run_with_context::<Self, _, _, _>(&config, task).await;
+
+        if let Err(message) = run.into_message_result::<Self>() {
+            panic!("{}", message);
+        }
+    }
+
+    /// Run a Result-emitting test task with every option in the config, enforcing the deadlines of each phase
+    /// and retrying with a fresh lifecycle up to `config.retries` times, failing with the last failure
+    async fn test_with_config<F, T>(config: TestConfig, task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>) -> F,
+    {
+        let mut retry = 0;
+
+        loop {
+            let run = lifecycle::run::<Self, _, _, _>(&config, &task).await;
+
+            match run.into_result() {
+                Ok(()) => return,
+                Err(_) if retry < config.retries => retry += 1,
+                Err(failure) => panic!("{}", failure.message::<Self>()),
+            }
         }
+    }
+
+    /// Run a Result-emitting test task under a name with every option in the config, like `test_with_config`,
+    /// handing the task a context whose `Context::metadata` names the test and the current attempt
+    async fn test_with_metadata<F, T>(name: &str, config: TestConfig, task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Context<Self>) -> F,
+    {
+        let total_attempts = config.retries + 1;
+
+        for attempt in 1..=total_attempts {
+            let metadata = Metadata {
+                name: Some(name.to_string()),
+                attempt,
+                total_attempts,
+                param_debug: None,
+            };
+            let run = lifecycle::run_with_metadata::<Self, _, _, _>(&config, metadata, &task).await;
+
+            match run.into_message_result::<Self>() {
+                Ok(()) => return,
+                Err(_) if attempt < total_attempts => continue,
+                Err(message) => panic!("{}", message),
+            }
+        }
+    }
+
+    /// Run a Result-emitting test task up to `attempts` times, each with a fresh lifecycle, failing with the last error
+    async fn test_with_retries<F, T>(attempts: usize, task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>) -> F,
+    {
+        Self::test_with_retry_policy(RetryPolicy::fixed(attempts, Duration::ZERO), task).await
+    }
+
+    /// Run a Result-emitting test task until it passes, waiting between attempts according to the policy
+    async fn test_with_retry_policy<F, T>(policy: RetryPolicy, task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>) -> F,
+    {
+        let started = Instant::now();
+        let mut retry = 0;
+
+        loop {
+            let error = match Self::try_test(&task).await {
+                Ok(()) => return,
+                Err(error) => error,
+            };
+
+            let delay = policy.backoff.delay(retry);
+            let exhausted = retry as usize + 1 >= policy.attempts
+                || policy
+                    .max_elapsed
+                    .is_some_and(|max_elapsed| started.elapsed() + delay > max_elapsed);
+
+            if exhausted {
+                panic!("{}", Self::fmt_failure(&error));
+            }
+
+            sleep(delay).await;
+            retry += 1;
+        }
+    }
+
+    /// Run a Result-emitting test task until it passes, like `test_with_retries`, but report a Flaky outcome with
+    /// a warning instead of failing if every attempt fails, unless `fail_on_flaky` says otherwise
+    async fn test_allow_flaky<F, T>(attempts: usize, task: T) -> TestOutcome<Self::Error>
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>) -> F,
+    {
+        let mut errors = Vec::new();
+
+        for _ in 0..attempts.max(1) {
+            match Self::try_test(&task).await {
+                Ok(()) => return TestOutcome::Passed,
+                Err(error) => errors.push(error),
+            }
+        }
+
+        let failures = errors
+            .iter()
+            .enumerate()
+            .map(|(index, error)| {
+                format!("attempt {} failed: {}", index + 1, Self::fmt_failure(error))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if Self::fail_on_flaky() {
+            panic!("{}", failures);
+        }
+
+        trace::warn(&format!("flaky test failed every attempt:\n{}", failures));
+
+        TestOutcome::Flaky(errors)
+    }
+
+    /// Run a Result-emitting test task once per parameter, each with its own lifecycle, reporting every failing
+    /// or panicking case
+    async fn test_each<I, P, F, T>(params: I, task: T)
+    where
+        I: IntoIterator<Item = P> + Send,
+        I::IntoIter: Send,
+        P: std::fmt::Debug + Send,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>, P) -> F,
+    {
+        Self::test_each_with_config(TestConfig::default(), params, task).await
+    }
+
+    /// Run a Result-emitting test task once per parameter, each with its own lifecycle and the config's deadlines,
+    /// reporting every failing case, or stopping at the first one with `FailureMode::FailFast` and reporting
+    /// the cases that didn't run as skipped
+    ///
+    /// ```
+    /// use spekt::{FailureMode, Test, TestConfig};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static RUNS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// struct Fixture;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Fixture {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// async fn check_even(failure_mode: FailureMode) -> String {
+    ///     let config = TestConfig::builder().failure_mode(failure_mode).build();
+    ///     let run = tokio::spawn(Fixture::test_each_with_config(config, [1, 2, 3], |_, number| async move {
+    ///         RUNS.fetch_add(1, Ordering::SeqCst);
+    ///         anyhow::ensure!(number % 2 == 0, "{} is odd", number);
+    ///
+    ///         Ok(())
+    ///     }));
+    ///     let payload = run.await.unwrap_err().into_panic();
+    ///
+    ///     spekt::panic_message(&*payload)
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// std::panic::set_hook(Box::new(|_| {}));
+    ///
+    /// let message = check_even(FailureMode::FailFast).await;
+    ///
+    /// assert_eq!(message, "case 1 failed: 1 is odd\ncase 2 skipped\ncase 3 skipped");
+    /// assert_eq!(RUNS.swap(0, Ordering::SeqCst), 1);
+    ///
+    /// let message = check_even(FailureMode::RunAll).await;
+    ///
+    /// assert_eq!(message, "case 1 failed: 1 is odd\ncase 3 failed: 3 is odd");
+    /// assert_eq!(RUNS.load(Ordering::SeqCst), 3);
+    /// # }
+    /// ```
+    async fn test_each_with_config<I, P, F, T>(config: TestConfig, params: I, task: T)
+    where
+        I: IntoIterator<Item = P> + Send,
+        I::IntoIter: Send,
+        P: std::fmt::Debug + Send,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>, P) -> F,
+    {
+        let mut failures = Vec::new();
+        let mut stopped = false;
+
+        for param in params {
+            let case = format!("{:?}", param);
+
+            if stopped {
+                failures.push(format!("case {} skipped", case));
+                continue;
+            }
+
+            let run = catch_unwind(lifecycle::run::<Self, _, _, _>(&config, |state| {
+                task(state, param)
+            }))
+            .await;
 
-        if let Err(error) = after {
-            assert!(false, format!("{}", error));
+            let failure = match run {
+                Err(payload) => lifecycle::panic_failure::<Self>(&*payload),
+                Ok(run) => match run.into_result() {
+                    Err(failure) => failure.message::<Self>(),
+                    Ok(()) => continue,
+                },
+            };
+
+            failures.push(format!("case {} failed: {}", case, failure));
+            stopped = config.failure_mode == FailureMode::FailFast;
+        }
+
+        if !failures.is_empty() {
+            panic!("{}", failures.join("\n"));
+        }
+    }
+
+    /// Run a Result-emitting test task once per parameter, each with its own lifecycle, handing the task a context
+    /// whose `Context::metadata` carries the parameter's `Debug` representation, and reporting every failing case
+    async fn test_each_with_context<I, P, F, T>(params: I, task: T)
+    where
+        I: IntoIterator<Item = P> + Send,
+        I::IntoIter: Send,
+        P: std::fmt::Debug + Send,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Context<Self>, P) -> F,
+    {
+        let config = TestConfig::default();
+        let mut failures = Vec::new();
+
+        for param in params {
+            let case = format!("{:?}", param);
+            let metadata = Metadata {
+                param_debug: Some(case.clone()),
+                ..Metadata::default()
+            };
+            let run = catch_unwind(lifecycle::run_with_metadata::<Self, _, _, _>(
+                &config,
+                metadata,
+                |context| task(context, param),
+            ))
+            .await;
+
+            let failure = match run {
+                Err(payload) => lifecycle::panic_failure::<Self>(&*payload),
+                Ok(run) => match run.into_message_result::<Self>() {
+                    Err(message) => message,
+                    Ok(()) => continue,
+                },
+            };
+
+            failures.push(format!("case {} failed: {}", case, failure));
+        }
+
+        if !failures.is_empty() {
+            panic!("{}", failures.join("\n"));
+        }
+    }
+
+    /// Run a Result-emitting test task's whole lifecycle once per input drawn from a proptest strategy,
+    /// shrinking the first failing input to a minimal one, with state built and torn down for every attempt
+    #[cfg(feature = "proptest")]
+    async fn test_prop<St, F, T>(strategy: St, task: T)
+    where
+        St: proptest::strategy::Strategy + Send + Sync,
+        St::Tree: Send,
+        St::Value: Send,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>, St::Value) -> F,
+    {
+        use proptest::{strategy::ValueTree, test_runner::TestRunner};
+
+        let mut runner = TestRunner::default();
+        let cases = runner.config().cases;
+        let max_shrink_iters = runner.config().max_shrink_iters;
+
+        for _ in 0..cases {
+            let mut tree = match strategy.new_tree(&mut runner) {
+                Err(reason) => panic!("failed to generate an input: {}", reason),
+                Ok(tree) => tree,
+            };
+            let input = tree.current();
+            let mut failure = match lifecycle::check::<Self, _, _>(|state| task(state, input)).await
+            {
+                None => continue,
+                Some(message) => (tree.current(), message),
+            };
+
+            if tree.simplify() {
+                for _ in 0..max_shrink_iters {
+                    let input = tree.current();
+
+                    match lifecycle::check::<Self, _, _>(|state| task(state, input)).await {
+                        Some(message) => {
+                            failure = (tree.current(), message);
+
+                            if !tree.simplify() {
+                                break;
+                            }
+                        }
+                        None => {
+                            if !tree.complicate() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+
+            panic!("minimal failing input {:?}: {}", failure.0, failure.1);
+        }
+    }
+
+    /// Run a Result-emitting test task's whole lifecycle repeatedly, building fresh state for every iteration,
+    /// and fail with the first failing iteration and the number that passed
+    async fn test_repeated<F, T>(count: usize, task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>) -> F,
+    {
+        let mut passed = 0;
+        let mut first_failure = None;
+
+        for iteration in 1..=count {
+            match lifecycle::try_run::<Self, _, _>(&task).await {
+                Err(error) => {
+                    first_failure.get_or_insert((iteration, Self::fmt_failure(&error)));
+                }
+                Ok(()) => passed += 1,
+            }
         }
+
+        if let Some((iteration, message)) = first_failure {
+            panic!(
+                "iteration {} failed first: {} ({} of {} passed)",
+                iteration, message, passed, count
+            );
+        }
+    }
+
+    /// Run a Result-emitting test task once per combination of two sets of parameters, each with its own lifecycle,
+    /// reporting every failing case by its `(a, b)` pair
+    async fn test_matrix<A, B, F, T>(a: Vec<A>, b: Vec<B>, task: T)
+    where
+        A: Clone + std::fmt::Debug + Send,
+        B: Clone + std::fmt::Debug + Send + Sync,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>, A, B) -> F,
+    {
+        let cases: Vec<(A, B)> = a
+            .into_iter()
+            .flat_map(|a| b.iter().map(move |b| (a.clone(), b.clone())))
+            .collect();
+
+        Self::test_each(cases, |state, (a, b)| task(state, a, b)).await
+    }
+
+    /// Measure a Result-emitting test task's steady-state duration by running it repeatedly against state built
+    /// by a single before and after, discarding the warmup iterations. A test skipped by before measures nothing
+    async fn bench<F, T>(iterations: usize, warmup: usize, task: T) -> BenchStats
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>) -> F,
+    {
+        let samples = lifecycle::try_run_returning::<Self, _, _, _>(|state| async move {
+            for _ in 0..warmup {
+                task(Arc::clone(&state)).await?;
+            }
+
+            let mut samples = Vec::with_capacity(iterations);
+
+            for _ in 0..iterations {
+                let started = Instant::now();
+
+                task(Arc::clone(&state)).await?;
+                samples.push(started.elapsed());
+            }
+
+            Ok(samples)
+        })
+        .await;
+
+        match samples {
+            Err(error) => panic!("{}", Self::fmt_failure(&error)),
+            Ok(samples) => BenchStats::from_samples(samples.unwrap_or_default()),
+        }
+    }
+
+    /// Run a Result-emitting test task twice against state built by a single before and after, failing with
+    /// whichever invocation failed, to catch operations like migrations or upserts that aren't idempotent
+    ///
+    /// ```
+    /// use spekt::Test;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// #[derive(Default)]
+    /// struct Migrations {
+    ///     applied: AtomicUsize,
+    /// }
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Migrations {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self::default())
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// std::panic::set_hook(Box::new(|_| {}));
+    ///
+    /// let run = tokio::spawn(Migrations::test_twice(|migrations| async move {
+    ///     let applied = migrations.applied.fetch_add(1, Ordering::SeqCst);
+    ///     anyhow::ensure!(applied == 0, "table users already exists");
+    ///
+    ///     Ok(())
+    /// }));
+    /// let payload = run.await.unwrap_err().into_panic();
+    ///
+    /// assert_eq!(
+    ///     spekt::panic_message(&*payload),
+    ///     "second invocation failed: table users already exists"
+    /// );
+    /// # }
+    /// ```
+    async fn test_twice<F, T>(task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + Fn(Arc<Self>) -> F,
+    {
+        let failure = lifecycle::try_run_returning::<Self, _, _, _>(|state| async move {
+            for invocation in ["first", "second"] {
+                if let Err(error) = task(Arc::clone(&state)).await {
+                    return Ok(Some(format!(
+                        "{} invocation failed: {}",
+                        invocation,
+                        Self::fmt_failure(&error)
+                    )));
+                }
+            }
+
+            Ok(None)
+        })
+        .await;
+
+        match failure {
+            Err(error) => panic!("{}", Self::fmt_failure(&error)),
+            Ok(Some(Some(message))) => panic!("{}", message),
+            Ok(_) => {}
+        }
+    }
+
+    /// Run a series of read-only test tasks sequentially against state built by a single before and after
+    async fn test_shared<F, T>(tasks: Vec<T>)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        let failures = lifecycle::shared::<Self, _, _>(&TestConfig::default(), tasks).await;
+
+        if !failures.is_empty() {
+            panic!("{}", failures.join("\n"));
+        }
+    }
+
+    /// Run a series of test tasks, sharing the test's state between them according to `config.isolation`,
+    /// and enforcing the config's deadlines on each lifecycle and each task
+    ///
+    /// ```
+    /// use spekt::{Isolation, Test, TestConfig};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static BUILDS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// struct Database;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Database {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         BUILDS.fetch_add(1, Ordering::SeqCst);
+    ///
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let task = |_| async { Ok(()) };
+    ///
+    /// for (isolation, builds) in [
+    ///     (Isolation::Fresh, 3),
+    ///     (Isolation::Shared, 1),
+    ///     (Isolation::Persistent, 1),
+    ///     (Isolation::Persistent, 0),
+    /// ] {
+    ///     let config = TestConfig::builder().isolation(isolation).build();
+    ///
+    ///     BUILDS.store(0, Ordering::SeqCst);
+    ///     Database::test_isolated(config, vec![task, task, task]).await;
+    ///
+    ///     assert_eq!(BUILDS.load(Ordering::SeqCst), builds);
+    /// }
+    ///
+    /// Database::release_persistent().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn test_isolated<F, T>(config: TestConfig, tasks: Vec<T>)
+    where
+        Self: 'static,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        let failures = match config.isolation {
+            Isolation::Fresh => {
+                let mut failures = Vec::new();
+
+                for (index, task) in tasks.into_iter().enumerate() {
+                    let failure =
+                        match catch_unwind(lifecycle::run::<Self, _, _, _>(&config, task)).await {
+                            Err(payload) => lifecycle::panic_failure::<Self>(&*payload),
+                            Ok(run) => match run.into_result() {
+                                Err(failure) => failure.message::<Self>(),
+                                Ok(()) => continue,
+                            },
+                        };
+
+                    failures.push(format!("task {} failed: {}", index, failure));
+                }
+
+                failures
+            }
+            Isolation::Shared => lifecycle::shared::<Self, _, _>(&config, tasks).await,
+            Isolation::Persistent => match lifecycle::persistent::<Self>(&config).await {
+                Err(Failure::Skipped(_)) => Vec::new(),
+                Err(failure) => vec![failure.message::<Self>()],
+                Ok(state) => lifecycle::each(&state, config.test_timeout, tasks).await,
+            },
+        };
+
+        if !failures.is_empty() {
+            panic!("{}", failures.join("\n"));
+        }
+    }
+
+    /// Tear down the process-wide state shared by `Isolation::Persistent` runs with after() and the clean-up its
+    /// setup registered, so that the next persistent run builds it again. Does nothing if no persistent run has
+    /// built the state
+    async fn release_persistent() -> Result<(), Self::Error>
+    where
+        Self: 'static,
+    {
+        lifecycle::release_persistent::<Self>().await
+    }
+
+    /// Run independent test tasks concurrently, each with its own lifecycle, reporting every failure
+    async fn test_parallel<F, T>(tasks: Vec<T>, max_concurrency: usize)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        Self::test_parallel_with_config(TestConfig::default(), tasks, max_concurrency).await
+    }
+
+    /// Run independent test tasks concurrently, each with its own lifecycle and the config's deadlines, reporting
+    /// every failure, or with `FailureMode::FailFast`, starting no new tasks after the first failure and
+    /// reporting the tasks that didn't start as skipped
+    async fn test_parallel_with_config<F, T>(
+        config: TestConfig,
+        tasks: Vec<T>,
+        max_concurrency: usize,
+    ) where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        let stopped = AtomicBool::new(false);
+        let runs = tasks
+            .into_iter()
+            .map(|task| {
+                let (config, stopped) = (&config, &stopped);
+
+                async move {
+                    if stopped.load(Ordering::SeqCst) {
+                        return None;
+                    }
+
+                    let failure =
+                        match catch_unwind(lifecycle::run::<Self, _, _, _>(config, task)).await {
+                            Err(payload) => Some(lifecycle::panic_failure::<Self>(&*payload)),
+                            Ok(run) => run
+                                .into_result()
+                                .err()
+                                .map(|failure| failure.message::<Self>()),
+                        };
+
+                    if failure.is_some() && config.failure_mode == FailureMode::FailFast {
+                        stopped.store(true, Ordering::SeqCst);
+                    }
+
+                    Some(failure)
+                }
+            })
+            .collect();
+
+        let failures: Vec<_> = join_bounded(runs, max_concurrency)
+            .await
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, run)| match run {
+                None => Some(format!("task {} skipped", index)),
+                Some(Some(failure)) => Some(format!("task {} failed: {}", index, failure)),
+                Some(None) => None,
+            })
+            .collect();
+
+        if !failures.is_empty() {
+            panic!("{}", failures.join("\n"));
+        }
+    }
+
+    /// Run independent test tasks concurrently by spawning each lifecycle onto an executor, reporting every failure
+    ///
+    /// ```
+    /// use spekt::{BoxFuture, Spawner, Test};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// #[derive(Default)]
+    /// struct RecordingSpawner {
+    ///     spawned: AtomicUsize,
+    /// }
+    ///
+    /// impl Spawner for RecordingSpawner {
+    ///     fn spawn(&self, future: BoxFuture<'static, ()>) {
+    ///         self.spawned.fetch_add(1, Ordering::SeqCst);
+    ///         tokio::spawn(future);
+    ///     }
+    /// }
+    ///
+    /// struct Fixture;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Fixture {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let spawner = RecordingSpawner::default();
+    /// let task = |_| async { Ok(()) };
+    ///
+    /// Fixture::test_parallel_spawned(&spawner, vec![task, task]).await;
+    ///
+    /// assert_eq!(spawner.spawned.load(Ordering::SeqCst), 2);
+    /// # }
+    /// ```
+    async fn test_parallel_spawned<F, T>(spawner: &dyn Spawner, tasks: Vec<T>)
+    where
+        Self: 'static,
+        Self::Error: 'static,
+        F: Future<Output = Result<(), Self::Error>> + Send + 'static,
+        T: Send + Sync + 'static + FnOnce(Arc<Self>) -> F,
+    {
+        let runs: Vec<_> = tasks
+            .into_iter()
+            .map(|task| spawn(spawner, lifecycle::try_run::<Self, _, _>(task)))
+            .collect();
+        let mut failures = Vec::new();
+
+        for (index, run) in runs.into_iter().enumerate() {
+            let failure = match run.await {
+                Err(payload) => lifecycle::panic_failure::<Self>(&*payload),
+                Ok(Err(error)) => Self::fmt_failure(&error),
+                Ok(Ok(())) => continue,
+            };
+
+            failures.push(format!("task {} failed: {}", index, failure));
+        }
+
+        if !failures.is_empty() {
+            panic!("{}", failures.join("\n"));
+        }
+    }
+
+    /// Run a series of test tasks, each with its own lifecycle, between before_all and after_all
+    async fn suite<I, F, T>(tasks: I)
+    where
+        I: IntoIterator<Item = T> + Send,
+        I::IntoIter: Send,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        lifecycle::suite::<Self, _, _, _>(&TestConfig::default(), None, tasks).await
+    }
+
+    /// Run a series of test tasks, each with its own lifecycle and the config's deadlines and after policy,
+    /// between before_all and after_all. With `detach_after`, the after() of each passing task runs in the
+    /// background on the current runtime (with the `tokio` or `async-std` feature) instead of blocking the next
+    /// task, failing the suite once every task has run; without a known runtime, after() runs inline
+    ///
+    /// ```should_panic
+    /// use spekt::{Test, TestConfig};
+    ///
+    /// struct Logs;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Logs {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    ///
+    ///     async fn after(&self) -> Result<(), Self::Error> {
+    ///         anyhow::bail!("failed to purge logs")
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let config = TestConfig::builder().detach_after(true).build();
+    /// let task = |_| async { Ok(()) };
+    ///
+    /// Logs::suite_with_config(config, vec![task, task]).await;
+    /// # }
+    /// ```
+    async fn suite_with_config<I, F, T>(config: TestConfig, tasks: I)
+    where
+        Self: 'static,
+        Self::Error: 'static,
+        I: IntoIterator<Item = T> + Send,
+        I::IntoIter: Send,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        let detached = current_spawner()
+            .filter(|_| config.detach_after)
+            .map(|spawner| Detached::<Self>::new(spawner, config.after_timeout));
+        let runner = detached
+            .as_ref()
+            .map(|detached| AfterRunner::Background(detached as &dyn Background<Self>));
+
+        lifecycle::suite::<Self, _, _, _>(&config, runner.as_ref(), tasks).await
+    }
+
+    /// Run a Result-emitting test task to completion on the current thread, without an async runtime.
+    /// Panics instead of deadlocking when called from inside another blocking test or, with the `tokio` feature,
+    /// a tokio runtime. Without the feature, or inside any other runtime, the refusal can't be detected: blocking
+    /// there deadlocks as soon as the task waits on that runtime, so await `test` instead
+    fn test_blocking<F, T>(task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        if let Err(error) = block_on(Self::test(task)) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Run a Result-emitting test task to completion on the current thread, without an async runtime, returning
+    /// the first error encountered. Refusing to block inside another blocking test or, with the `tokio` feature,
+    /// a tokio runtime is returned as an `EnterError` instead of a panic, with the same limits as `test_blocking`
+    ///
+    /// ```
+    /// use spekt::{EnterError, Test};
+    ///
+    /// struct Fixture;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Fixture {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// let error = Fixture::try_test_blocking(|_| async {
+    ///     Fixture::try_test_blocking(|_| async { Ok(()) })
+    /// })
+    /// .unwrap_err();
+    ///
+    /// assert!(error.is::<EnterError>());
+    /// ```
+    fn try_test_blocking<F, T>(task: T) -> Result<(), Self::Error>
+    where
+        Self::Error: From<EnterError>,
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        block_on(Self::try_test(task))?
+    }
+
+    /// Run a Result-emitting test task to completion on the current thread with async-std's executor,
+    /// for fixtures that depend on async-std's reactor
+    ///
+    /// ```
+    /// use spekt::Test;
+    /// use std::time::Duration;
+    ///
+    /// struct Fixture;
+    ///
+    /// #[spekt::async_trait]
+    /// impl Test for Fixture {
+    ///     type Error = anyhow::Error;
+    ///
+    ///     async fn before() -> Result<Self, Self::Error> {
+    ///         async_std::task::sleep(Duration::from_millis(1)).await;
+    ///
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// Fixture::test_blocking_async_std(|_| async move {
+    ///     async_std::task::yield_now().await;
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    #[cfg(feature = "async-std")]
+    fn test_blocking_async_std<F, T>(task: T)
+    where
+        F: Future<Output = Result<(), Self::Error>> + Send,
+        T: Send + Sync + FnOnce(Arc<Self>) -> F,
+    {
+        async_std::task::block_on(Self::test(task))
     }
 }
@@ -0,0 +1,266 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex, OnceLock},
+    task::{Context, Poll, Waker},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Error returned when a future fails to complete before its deadline
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeoutError(pub(crate) Duration);
+
+impl TimeoutError {
+    /// The deadline that was exceeded
+    pub fn duration(&self) -> Duration {
+        self.0
+    }
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "timed out after {:?}", self.0)
+    }
+}
+
+impl std::error::Error for TimeoutError {}
+
+/// Slot for the waker of a pending `Delay`, emptied by the timer thread once its deadline passes
+type Slot = Arc<Mutex<Option<Waker>>>;
+
+/// Deadlines of every pending `Delay`, soonest first, along with the slots of their wakers
+#[derive(Default)]
+struct Deadlines {
+    heap: BinaryHeap<Reverse<(Instant, u64)>>,
+    slots: HashMap<u64, Slot>,
+    next_id: u64,
+}
+
+/// Single thread waking every `Delay` in the process once its deadline passes
+struct Timer {
+    deadlines: Mutex<Deadlines>,
+    changed: Condvar,
+}
+
+impl Timer {
+    /// The process-wide timer, starting its thread on first use
+    fn shared() -> &'static Self {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+
+        TIMER.get_or_init(|| {
+            thread::Builder::new()
+                .name("spekt-timer".to_string())
+                .spawn(|| Timer::shared().run())
+                .expect("failed to start the timer thread");
+
+            Self {
+                deadlines: Mutex::new(Deadlines::default()),
+                changed: Condvar::new(),
+            }
+        })
+    }
+
+    /// Wake the slot once the deadline passes
+    fn register(&self, deadline: Instant, slot: Slot) {
+        let mut deadlines = self.deadlines.lock().unwrap();
+        let id = deadlines.next_id;
+
+        deadlines.next_id += 1;
+        deadlines.heap.push(Reverse((deadline, id)));
+        deadlines.slots.insert(id, slot);
+
+        self.changed.notify_one();
+    }
+
+    /// Sleep until the soonest deadline, or until a new one is registered, waking every slot that's due
+    fn run(&self) {
+        let mut deadlines = self.deadlines.lock().unwrap();
+
+        loop {
+            let now = Instant::now();
+
+            while let Some(Reverse((deadline, id))) = deadlines.heap.peek().copied() {
+                if deadline > now {
+                    break;
+                }
+
+                deadlines.heap.pop();
+
+                if let Some(waker) = deadlines
+                    .slots
+                    .remove(&id)
+                    .and_then(|slot| slot.lock().unwrap().take())
+                {
+                    waker.wake();
+                }
+            }
+
+            deadlines = match deadlines.heap.peek() {
+                None => self.changed.wait(deadlines).unwrap(),
+                Some(Reverse((deadline, _))) => {
+                    let timeout = deadline.saturating_duration_since(now);
+
+                    self.changed.wait_timeout(deadlines, timeout).unwrap().0
+                }
+            };
+        }
+    }
+}
+
+/// Runtime-agnostic future that completes once its deadline has passed, woken by a single timer thread shared
+/// by every pending delay
+pub struct Delay {
+    deadline: Instant,
+    waker: Option<Slot>,
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        match &self.waker {
+            Some(waker) => {
+                *waker.lock().unwrap() = Some(context.waker().clone());
+            }
+            None => {
+                let waker = Arc::new(Mutex::new(Some(context.waker().clone())));
+
+                Timer::shared().register(self.deadline, Arc::clone(&waker));
+                self.waker = Some(waker);
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Delay {
+    fn drop(&mut self) {
+        if let Some(waker) = &self.waker {
+            waker.lock().unwrap().take();
+        }
+    }
+}
+
+/// Wait for a duration without blocking the executor
+pub fn sleep(duration: Duration) -> Delay {
+    Delay {
+        deadline: Instant::now() + duration,
+        waker: None,
+    }
+}
+
+/// Future that fails with a TimeoutError if the inner future doesn't resolve in time
+pub struct Timeout<F> {
+    future: Pin<Box<F>>,
+    delay: Delay,
+    duration: Duration,
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, TimeoutError>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(output) = self.future.as_mut().poll(context) {
+            return Poll::Ready(Ok(output));
+        }
+
+        match Pin::new(&mut self.delay).poll(context) {
+            Poll::Ready(()) => Poll::Ready(Err(TimeoutError(self.duration))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Race a future against a deadline
+pub fn timeout<F: Future>(duration: Duration, future: F) -> Timeout<F> {
+    Timeout {
+        future: Box::pin(future),
+        delay: sleep(duration),
+        duration,
+    }
+}
+
+/// Race a lifecycle phase against its deadline, through tokio's timer when running inside a tokio runtime.
+/// That runtime must have its timer enabled, like the ones built by `#[tokio::test]` and `#[tokio::main]`:
+/// tokio panics otherwise, failing the phase with a message asking for `enable_time`
+#[cfg(feature = "tokio")]
+pub(crate) async fn phase_timeout<F: Future>(
+    duration: Duration,
+    future: F,
+) -> Result<F::Output, TimeoutError> {
+    if tokio::runtime::Handle::try_current().is_err() {
+        return timeout(duration, future).await;
+    }
+
+    tokio::time::timeout(duration, future)
+        .await
+        .map_err(|_| TimeoutError(duration))
+}
+
+/// Race a lifecycle phase against its deadline
+#[cfg(not(feature = "tokio"))]
+pub(crate) async fn phase_timeout<F: Future>(
+    duration: Duration,
+    future: F,
+) -> Result<F::Output, TimeoutError> {
+    timeout(duration, future).await
+}
+
+/// Poll an async condition on an interval until it holds, failing once the timeout elapses.
+///
+/// ```
+/// use spekt::{eventually, Test};
+/// use std::{
+///     sync::atomic::{AtomicUsize, Ordering},
+///     time::Duration,
+/// };
+///
+/// struct Queue {
+///     pending: AtomicUsize,
+/// }
+///
+/// #[spekt::async_trait]
+/// impl Test for Queue {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self {
+///             pending: AtomicUsize::new(3),
+///         })
+///     }
+/// }
+///
+/// Queue::test_blocking(|queue| async move {
+///     eventually(Duration::from_secs(1), Duration::from_millis(10), || async {
+///         queue.pending.fetch_sub(1, Ordering::SeqCst) == 1
+///     })
+///     .await?;
+///
+///     Ok(())
+/// });
+/// ```
+pub async fn eventually<F, Fut>(
+    timeout: Duration,
+    interval: Duration,
+    mut condition: F,
+) -> Result<(), TimeoutError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    self::timeout(timeout, async {
+        while !condition().await {
+            sleep(interval).await;
+        }
+    })
+    .await
+}
@@ -0,0 +1,40 @@
+use crate::lifecycle::Phase;
+use std::future::Future;
+
+/// Await a lifecycle phase inside a span named after it, recording the phase's duration on close
+#[cfg(feature = "tracing")]
+pub(crate) async fn traced<F: Future>(phase: Phase, future: F) -> F::Output {
+    use std::time::Instant;
+    use tracing::{field, info_span, Instrument};
+
+    let span = match phase {
+        Phase::Before => info_span!("spekt.before", duration_ms = field::Empty),
+        Phase::Test => info_span!("spekt.test", duration_ms = field::Empty),
+        Phase::After => info_span!("spekt.after", duration_ms = field::Empty),
+    };
+
+    let started = Instant::now();
+    let output = future.instrument(span.clone()).await;
+
+    span.record("duration_ms", started.elapsed().as_millis() as u64);
+
+    output
+}
+
+/// Await a lifecycle phase without tracing
+#[cfg(not(feature = "tracing"))]
+pub(crate) async fn traced<F: Future>(_phase: Phase, future: F) -> F::Output {
+    future.await
+}
+
+/// Emit a warning as a tracing event
+#[cfg(feature = "tracing")]
+pub(crate) fn warn(message: &str) {
+    tracing::warn!("{}", message);
+}
+
+/// Emit a warning on stderr without tracing
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn warn(message: &str) {
+    eprintln!("warning: {}", message);
+}
@@ -0,0 +1,230 @@
+use crate::{
+    config::TestConfig,
+    context::Context,
+    lifecycle,
+    outcome::TestOutcome,
+    test::{BoxFuture, Test},
+    unwind::{catch_unwind, panic_message},
+};
+use std::{collections::HashMap, fmt, future::Future, panic, sync::Arc};
+
+/// Task of a single case, boxed so that cases with different closures can share a workflow
+type Task<S> =
+    Box<dyn FnOnce(Arc<S>) -> BoxFuture<'static, Result<(), <S as Test>::Error>> + Send + Sync>;
+
+/// Named case of a workflow, along with the names of the cases it depends on
+struct Case<S: Test> {
+    name: String,
+    dependencies: Vec<String>,
+    task: Task<S>,
+}
+
+/// Suite of named cases that depend on each other's success, like create -> update -> delete, run between
+/// before_all and after_all with a lifecycle per case. Cases run in dependency order (and otherwise in the order
+/// they were added), and a case whose dependency didn't pass is skipped instead of failing in a confusing way.
+/// Every case, skipped or not, is observed by the fixture's `on_start` and `on_finish` hooks
+///
+/// ```
+/// use spekt::{Test, TestOutcome, Workflow};
+///
+/// struct Api;
+///
+/// #[spekt::async_trait]
+/// impl Test for Api {
+///     type Error = anyhow::Error;
+///
+///     async fn before() -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let outcomes = Workflow::<Api>::new()
+///     .add_case("delete", &["update"], |_| async { Ok(()) })
+///     .add_case("create", &[], |_| async { anyhow::bail!("duplicate user") })
+///     .add_case("update", &["create"], |_| async { Ok(()) })
+///     .run()
+///     .await;
+///
+/// let names: Vec<_> = outcomes.iter().map(|(name, _)| name.as_str()).collect();
+/// assert_eq!(names, ["create", "update", "delete"]);
+///
+/// assert!(matches!(outcomes[0].1, TestOutcome::Failed(_)));
+/// assert!(outcomes[1].1.is_skipped());
+/// assert!(outcomes[2].1.is_skipped());
+/// # }
+/// ```
+pub struct Workflow<S: Test> {
+    cases: Vec<Case<S>>,
+}
+
+impl<S: Test + 'static> Workflow<S> {
+    /// Create a workflow without any cases
+    pub fn new() -> Self {
+        Self { cases: Vec::new() }
+    }
+
+    /// Add a named case that only runs once every case it depends on has passed
+    pub fn add_case<F, T>(mut self, name: impl Into<String>, dependencies: &[&str], task: T) -> Self
+    where
+        F: Future<Output = Result<(), S::Error>> + Send + 'static,
+        T: Send + Sync + 'static + FnOnce(Arc<S>) -> F,
+    {
+        self.cases.push(Case {
+            name: name.into(),
+            dependencies: dependencies.iter().map(ToString::to_string).collect(),
+            task: Box::new(move |state| Box::pin(task(state))),
+        });
+
+        self
+    }
+
+    /// Run every case in dependency order, reporting the outcome of each by name in the order they ran.
+    /// Panics if a case depends on an unknown case or the dependencies form a cycle, or if before_all fails
+    pub async fn run(self) -> Vec<(String, TestOutcome<S::Error>)> {
+        let cases = ordered(self.cases);
+
+        if let Err(error) = S::before_all().await {
+            panic!("{}", S::fmt_failure(&error));
+        }
+
+        let config = TestConfig::default();
+        let mut outcomes: Vec<(String, TestOutcome<S::Error>)> = Vec::new();
+
+        for case in cases {
+            S::on_start(&case.name).await;
+
+            let passed = |dependency: &String| {
+                outcomes
+                    .iter()
+                    .any(|(name, outcome)| name == dependency && outcome.is_passed())
+            };
+
+            if !case.dependencies.iter().all(passed) {
+                S::on_finish(&case.name, &TestOutcome::Skipped).await;
+                outcomes.push((case.name, TestOutcome::Skipped));
+                continue;
+            }
+
+            let task = case.task;
+            let task = |context: Context<S>| async move {
+                let state = Arc::clone(context.state());
+
+                match lifecycle::around_each(&*state, task(Arc::clone(&state))).await {
+                    Err(payload) => panic::resume_unwind(payload),
+                    Ok(result) => result,
+                }
+            };
+            let outcome = match catch_unwind(lifecycle::run_with_context::<S, _, _, _>(
+                &config, task,
+            ))
+            .await
+            {
+                Err(payload) => TestOutcome::Panicked(panic_message(&*payload)),
+                Ok(run) => run.into_outcome(),
+            };
+
+            S::on_finish(&case.name, &outcome).await;
+            outcomes.push((case.name, outcome));
+        }
+
+        if let Err(error) = S::after_all().await {
+            panic!("{}", S::fmt_failure(&error));
+        }
+
+        outcomes
+    }
+
+    /// Run every case in dependency order, failing with every case that didn't pass and every case skipped
+    /// because of it
+    pub async fn test(self) {
+        let failures: Vec<_> = self
+            .run()
+            .await
+            .into_iter()
+            .filter_map(|(name, outcome)| {
+                let failure = match outcome {
+                    TestOutcome::Passed | TestOutcome::Flaky(_) => return None,
+                    TestOutcome::Skipped => "skipped after a dependency didn't pass".to_string(),
+                    TestOutcome::Failed(error) => S::fmt_failure(&error),
+                    TestOutcome::TimedOut(duration) => format!("timed out after {:?}", duration),
+                    TestOutcome::Panicked(message) => format!("panicked: {}", message),
+                };
+
+                Some(format!("case \"{}\" {}", name, failure))
+            })
+            .collect();
+
+        if !failures.is_empty() {
+            panic!("{}", failures.join("\n"));
+        }
+    }
+}
+
+impl<S: Test + 'static> Default for Workflow<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Test> fmt::Debug for Workflow<S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cases: Vec<_> = self.cases.iter().map(|case| &case.name).collect();
+
+        formatter
+            .debug_struct("Workflow")
+            .field("cases", &cases)
+            .finish()
+    }
+}
+
+/// Sort cases so that every case comes after its dependencies, keeping the order they were added otherwise
+fn ordered<S: Test>(cases: Vec<Case<S>>) -> Vec<Case<S>> {
+    let names: HashMap<_, _> = cases
+        .iter()
+        .enumerate()
+        .map(|(index, case)| (case.name.clone(), index))
+        .collect();
+
+    for case in &cases {
+        if let Some(unknown) = case
+            .dependencies
+            .iter()
+            .find(|dependency| !names.contains_key(*dependency))
+        {
+            panic!(
+                "case \"{}\" depends on unknown case \"{}\"",
+                case.name, unknown
+            );
+        }
+    }
+
+    let mut remaining: Vec<_> = cases.into_iter().map(Some).collect();
+    let mut ordered = Vec::with_capacity(remaining.len());
+    let mut done = vec![false; remaining.len()];
+
+    while ordered.len() < remaining.len() {
+        let next = remaining.iter().position(|case| {
+            case.as_ref().is_some_and(|case| {
+                case.dependencies
+                    .iter()
+                    .all(|dependency| done[names[dependency]])
+            })
+        });
+
+        match next {
+            Some(index) => {
+                done[index] = true;
+                ordered.push(remaining[index].take().unwrap());
+            }
+            None => {
+                let stuck: Vec<_> = remaining.iter().flatten().map(|case| &case.name).collect();
+
+                panic!("cases {:?} depend on each other in a cycle", stuck);
+            }
+        }
+    }
+
+    ordered
+}
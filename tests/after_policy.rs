@@ -0,0 +1,82 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::{AfterPolicy, Test, TestConfig};
+use std::sync::Arc;
+
+struct Database;
+
+#[spekt::async_trait]
+impl Test for Database {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+fn config(policy: AfterPolicy) -> TestConfig {
+    TestConfig::builder().after_policy(policy).build()
+}
+
+async fn passing(policy: AfterPolicy) -> Vec<String> {
+    Database::test_with_config(config(policy), |_| async { Ok(()) }).await;
+
+    events()
+}
+
+async fn failing(policy: AfterPolicy) -> Vec<String> {
+    let message = panic_message(Database::test_with_config(
+        config(policy),
+        |_: Arc<Database>| async { anyhow::bail!("row missing") },
+    ))
+    .await;
+
+    assert_eq!(message, "row missing");
+    events()
+}
+
+async fn panicking(policy: AfterPolicy) -> Vec<String> {
+    let message = panic_message(Database::test_with_config(
+        config(policy),
+        |_: Arc<Database>| async { panic!("connection reset") },
+    ))
+    .await;
+
+    assert_eq!(message, "connection reset");
+    events()
+}
+
+#[tokio::test]
+async fn always_runs_after_whatever_the_outcome() {
+    assert_eq!(passing(AfterPolicy::Always).await, ["after"]);
+    assert_eq!(failing(AfterPolicy::Always).await, ["after"]);
+    assert_eq!(panicking(AfterPolicy::Always).await, ["after"]);
+}
+
+#[tokio::test]
+async fn on_success_only_runs_after_for_passing_tasks() {
+    assert_eq!(passing(AfterPolicy::OnSuccess).await, ["after"]);
+    assert!(failing(AfterPolicy::OnSuccess).await.is_empty());
+    assert!(panicking(AfterPolicy::OnSuccess).await.is_empty());
+}
+
+#[tokio::test]
+async fn on_failure_only_runs_after_for_failing_and_panicking_tasks() {
+    assert!(passing(AfterPolicy::OnFailure).await.is_empty());
+    assert_eq!(failing(AfterPolicy::OnFailure).await, ["after"]);
+    assert_eq!(panicking(AfterPolicy::OnFailure).await, ["after"]);
+}
+
+#[tokio::test]
+async fn never_skips_after_whatever_the_outcome() {
+    assert!(passing(AfterPolicy::Never).await.is_empty());
+    assert!(failing(AfterPolicy::Never).await.is_empty());
+    assert!(panicking(AfterPolicy::Never).await.is_empty());
+}
@@ -0,0 +1,131 @@
+use anyhow::Context as _;
+use spekt::{assert_eq_ok, assert_err_is, ensure, AssertionError, Test, TestConfig, TestOutcome};
+use std::{io, num::ParseIntError};
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+
+    fn assertion_error(error: AssertionError) -> Option<Self::Error> {
+        Some(error.into())
+    }
+}
+
+struct Unconverted;
+
+#[spekt::async_trait]
+impl Test for Unconverted {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+fn checked(value: u8) -> anyhow::Result<u8> {
+    match value {
+        0 => ensure!(value == 0, "unreachable"),
+        _ => assert_eq_ok!(value % 2, 0, "{} is odd", value),
+    }
+
+    Ok(value)
+}
+
+#[test]
+fn assertions_are_expressions() {
+    assert_eq!(checked(0).unwrap(), 0);
+    assert_eq!(checked(2).unwrap(), 2);
+    assert_eq!(
+        checked(3).unwrap_err().to_string(),
+        "assertion `left == right` failed: 1 != 0: 3 is odd"
+    );
+}
+
+#[tokio::test]
+async fn too_few_assertions_fail_the_test() {
+    let config = TestConfig::builder().min_assertions(2).build();
+    let outcome = Fixture::run_with_config(config, |_| async {
+        ensure!(true);
+
+        Ok(())
+    })
+    .await;
+
+    match outcome {
+        TestOutcome::Failed(error) => assert_eq!(
+            error.to_string(),
+            "expected at least 2 assertions, but the task made 1"
+        ),
+        outcome => panic!("unexpected outcome {:?}", outcome),
+    }
+}
+
+#[tokio::test]
+async fn enough_assertions_pass_the_test() {
+    let config = TestConfig::builder().min_assertions(2).build();
+    let outcome = Fixture::run_with_config(config, |_| async {
+        ensure!(true);
+        assert_eq_ok!(1, 1);
+
+        Ok(())
+    })
+    .await;
+
+    assert!(outcome.is_passed());
+}
+
+#[tokio::test]
+async fn unconverted_assertion_failures_panic() {
+    let config = TestConfig::builder()
+        .min_assertions(1)
+        .suppress_panic_output(true)
+        .build();
+    let outcome = Unconverted::run_with_config(config, |_| async { Ok(()) }).await;
+
+    match outcome {
+        TestOutcome::Panicked(message) => assert_eq!(
+            message,
+            "expected at least 1 assertions, but the task made 0"
+        ),
+        outcome => panic!("unexpected outcome {:?}", outcome),
+    }
+}
+
+fn parse(digits: &str) -> anyhow::Result<u8> {
+    digits.parse::<u8>().context("invalid port")
+}
+
+fn matched(digits: &str) -> anyhow::Result<String> {
+    let error = assert_err_is!(parse(digits), ParseIntError);
+
+    Ok(error.to_string())
+}
+
+#[test]
+fn err_is_assertions_downcast_temporaries() {
+    assert_eq!(matched("two").unwrap(), "invalid digit found in string");
+}
+
+#[test]
+fn err_is_assertions_reject_other_errors_and_successes() {
+    let rejected = || -> anyhow::Result<()> {
+        assert_err_is!(parse("two"), io::Error);
+
+        Ok(())
+    };
+
+    assert!(rejected()
+        .unwrap_err()
+        .to_string()
+        .ends_with("but got: invalid port"));
+    assert!(matched("2")
+        .unwrap_err()
+        .to_string()
+        .ends_with("but got Ok"));
+}
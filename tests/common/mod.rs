@@ -0,0 +1,44 @@
+//! Helpers shared by the integration tests, each of which runs on its own thread
+
+#![allow(dead_code)]
+
+use std::{any::Any, cell::RefCell, future::Future};
+
+thread_local! {
+    static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Record an event of the current test, like a lifecycle hook running
+pub fn record(event: impl Into<String>) {
+    EVENTS.with(|events| events.borrow_mut().push(event.into()));
+}
+
+/// Take every event the current test recorded so far
+pub fn events() -> Vec<String> {
+    EVENTS.with(|events| events.take())
+}
+
+/// Extract the message of a panic payload, if it panicked with one
+pub fn message_of(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_default(),
+    }
+}
+
+/// Run a future on the current thread's runtime, returning the message it panicked with
+pub async fn panic_message<F>(future: F) -> String
+where
+    F: Future + Send + 'static,
+    F::Output: Send,
+{
+    let payload = match tokio::spawn(future).await {
+        Err(error) => error.into_panic(),
+        Ok(_) => panic!("expected a panic"),
+    };
+
+    message_of(&*payload)
+}
@@ -0,0 +1,282 @@
+use spekt::{
+    AssertionError, BeforeOutcome, CleanupStack, ForkUnsupported, Resources, Test, TestConfig,
+    TestOutcome,
+};
+use std::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    time::Duration,
+};
+
+// every test runs on its own thread, so each records the hooks of its own fixtures
+thread_local! {
+    static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static SKIPPING: Cell<Option<&'static str>> = const { Cell::new(None) };
+    static UNFORKABLE: Cell<Option<&'static str>> = const { Cell::new(None) };
+    static STRICT: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+fn record(name: &str, hook: &str) {
+    EVENTS.with(|events| events.borrow_mut().push(format!("{} {}", name, hook)));
+}
+
+fn events() -> Vec<String> {
+    EVENTS.with(|events| events.take())
+}
+
+trait Name: Send + Sync {
+    const NAME: &'static str;
+}
+
+struct First;
+struct Second;
+struct Third;
+
+impl Name for First {
+    const NAME: &'static str = "first";
+}
+
+impl Name for Second {
+    const NAME: &'static str = "second";
+}
+
+impl Name for Third {
+    const NAME: &'static str = "third";
+}
+
+/// Fixture that records every hook it runs
+struct Part<N>(PhantomData<N>);
+
+#[spekt::async_trait]
+impl<N: Name> Test for Part<N> {
+    type Error = anyhow::Error;
+
+    fn fail_on_flaky() -> bool {
+        STRICT.with(Cell::get) == Some(N::NAME)
+    }
+
+    fn assertion_error(error: AssertionError) -> Option<Self::Error> {
+        (STRICT.with(Cell::get) == Some(N::NAME))
+            .then(|| anyhow::anyhow!("{} rejected: {}", N::NAME, error))
+    }
+
+    fn warn_slow_before(_elapsed: Duration, _threshold: Duration) {
+        record(N::NAME, "slow");
+    }
+
+    async fn env() -> Result<(), Self::Error> {
+        record(N::NAME, "env");
+
+        Ok(())
+    }
+
+    async fn before() -> Result<Self, Self::Error> {
+        record(N::NAME, "before");
+
+        Ok(Self(PhantomData))
+    }
+
+    async fn before_or_skip(
+        cleanup: &mut CleanupStack<Self::Error>,
+        resources: &mut Resources,
+    ) -> Result<BeforeOutcome<Self>, Self::Error> {
+        if SKIPPING.with(Cell::get) == Some(N::NAME) {
+            record(N::NAME, "skip");
+
+            return Ok(BeforeOutcome::Skip(format!("no {}", N::NAME)));
+        }
+
+        Self::before_with_resources(cleanup, resources)
+            .await
+            .map(BeforeOutcome::Ready)
+    }
+
+    async fn fork(&self) -> Result<Self, Self::Error>
+    where
+        Self::Error: From<ForkUnsupported>,
+    {
+        if UNFORKABLE.with(Cell::get) == Some(N::NAME) {
+            return Err(ForkUnsupported.into());
+        }
+
+        record(N::NAME, "fork");
+
+        Ok(Self(PhantomData))
+    }
+
+    async fn shutdown(&self) -> Result<(), Self::Error> {
+        record(N::NAME, "shutdown");
+
+        Ok(())
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record(N::NAME, "after");
+
+        Ok(())
+    }
+
+    async fn before_all() -> Result<(), Self::Error> {
+        record(N::NAME, "before_all");
+
+        Ok(())
+    }
+
+    async fn after_all() -> Result<(), Self::Error> {
+        record(N::NAME, "after_all");
+
+        Ok(())
+    }
+}
+
+type Pair = (Part<First>, Part<Second>);
+type Nested = ((Part<First>, Part<Second>), Part<Third>);
+
+#[tokio::test]
+async fn pairs_forward_env_and_teardown_hooks() {
+    let outcome = Pair::run(|_| async { Ok(()) }).await;
+
+    assert!(outcome.is_passed());
+    assert_eq!(
+        events(),
+        [
+            "first env",
+            "second env",
+            "first before",
+            "second before",
+            "second shutdown",
+            "first shutdown",
+            "second after",
+            "first after",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn pairs_skip_when_either_fixture_skips() {
+    SKIPPING.with(|skipping| skipping.set(Some("second")));
+
+    let outcome = Pair::run(|_| async { panic!("ran a skipped task") }).await;
+
+    assert!(outcome.is_skipped());
+    assert_eq!(
+        events(),
+        [
+            "first env",
+            "second env",
+            "first before",
+            "second skip",
+            "first after"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn nested_pairs_skip_when_an_inner_fixture_skips() {
+    SKIPPING.with(|skipping| skipping.set(Some("first")));
+
+    let outcome = Nested::run(|_| async { panic!("ran a skipped task") }).await;
+
+    assert!(outcome.is_skipped());
+    assert_eq!(
+        events(),
+        [
+            "first env",
+            "second env",
+            "third env",
+            "first skip",
+            "second before",
+            "second after",
+            "third before",
+            "third after",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn pairs_fork_every_fixture() {
+    Pair::test_with_context(|context| async move {
+        context.fork().await?;
+
+        Ok(())
+    })
+    .await;
+
+    let events = events();
+
+    assert_eq!(events[4..6], ["first fork", "second fork"]);
+    assert_eq!(events[6..8], ["second after", "first after"]);
+}
+
+#[tokio::test]
+async fn pairs_tear_down_the_forked_half_if_the_other_cannot_fork() {
+    UNFORKABLE.with(|unforkable| unforkable.set(Some("second")));
+
+    let report = Pair::test_reported_with_context(|context| async move {
+        context.fork().await?;
+
+        Ok(())
+    })
+    .await;
+
+    match report.outcome {
+        TestOutcome::Failed(error) => assert_eq!(error.to_string(), "fork unsupported"),
+        outcome => panic!("expected a failure, got {:?}", outcome),
+    }
+
+    assert_eq!(events()[4..6], ["first fork", "first after"]);
+}
+
+#[tokio::test]
+async fn pairs_warn_about_slow_setup_through_every_fixture() {
+    let config = TestConfig::builder()
+        .slow_before_threshold(Duration::ZERO)
+        .build();
+
+    Pair::test_with_config(config, |_| async { Ok(()) }).await;
+
+    assert_eq!(events()[4..6], ["first slow", "second slow"]);
+}
+
+#[tokio::test]
+async fn pairs_forward_suite_hooks() {
+    Pair::suite(vec![|_| async { Ok(()) }]).await;
+
+    let events = events();
+
+    assert_eq!(events[..2], ["first before_all", "second before_all"]);
+    assert_eq!(
+        events[events.len() - 2..],
+        ["second after_all", "first after_all"]
+    );
+}
+
+#[tokio::test]
+async fn pairs_fail_on_flaky_if_either_fixture_does() {
+    STRICT.with(|strict| strict.set(Some("second")));
+
+    let outcome = Pair::run(|_| async {
+        Pair::test_allow_flaky(2, |_| async { anyhow::bail!("flaky") }).await;
+
+        Ok(())
+    })
+    .await;
+
+    assert!(matches!(outcome, TestOutcome::Panicked(_)));
+}
+
+#[tokio::test]
+async fn pairs_convert_assertion_failures_through_either_fixture() {
+    STRICT.with(|strict| strict.set(Some("second")));
+
+    let config = TestConfig::builder().min_assertions(1).build();
+    let outcome = Pair::run_with_config(config, |_| async { Ok(()) }).await;
+
+    match outcome {
+        TestOutcome::Failed(error) => assert_eq!(
+            error.to_string(),
+            "second rejected: expected at least 1 assertions, but the task made 0"
+        ),
+        outcome => panic!("expected a failure, got {:?}", outcome),
+    }
+}
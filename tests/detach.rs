@@ -0,0 +1,46 @@
+#![cfg(feature = "tokio")]
+
+use spekt::{CleanupStack, Resources, Test, TestConfig};
+use std::{sync::Mutex, time::Duration};
+
+static EVENTS: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+struct Bucket;
+
+#[spekt::async_trait]
+impl Test for Bucket {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+
+    async fn before_with_resources(
+        cleanup: &mut CleanupStack<Self::Error>,
+        _resources: &mut Resources,
+    ) -> Result<Self, Self::Error> {
+        cleanup.defer(|| async {
+            EVENTS.lock().unwrap().push("delete bucket");
+
+            Ok(())
+        });
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        EVENTS.lock().unwrap().push("empty bucket");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn setup_cleanup_runs_after_detached_teardown() {
+    let config = TestConfig::builder().detach_after(true).build();
+
+    Bucket::suite_with_config(config, vec![|_| async { Ok(()) }]).await;
+
+    assert_eq!(*EVENTS.lock().unwrap(), ["empty bucket", "delete bucket"]);
+}
@@ -0,0 +1,135 @@
+use anyhow::Context as _;
+use spekt::{CompletedEarly, Test, TestOutcome};
+use std::fmt;
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[tokio::test]
+async fn completing_early_passes() {
+    let report = Fixture::test_reported_with_context(|context| async move {
+        context.complete_early()?;
+
+        anyhow::bail!("unreachable")
+    })
+    .await;
+
+    assert!(report.outcome.is_passed());
+}
+
+#[tokio::test]
+async fn errors_after_completing_early_fail() {
+    let report = Fixture::test_reported_with_context(|context| async move {
+        let _ = context.complete_early::<()>();
+
+        anyhow::bail!("boom")
+    })
+    .await;
+
+    match report.outcome {
+        TestOutcome::Failed(error) => assert_eq!(error.to_string(), "boom"),
+        outcome => panic!("unexpected outcome {:?}", outcome),
+    }
+}
+
+#[tokio::test]
+async fn errors_without_completing_early_fail() {
+    let report =
+        Fixture::test_reported_with_context(|_| async move { anyhow::bail!("boom") }).await;
+
+    assert!(matches!(report.outcome, TestOutcome::Failed(_)));
+}
+
+#[tokio::test]
+async fn completing_early_through_wrapped_errors_passes() {
+    let report = Fixture::test_reported_with_context(|context| async move {
+        context.complete_early::<()>().context("dataset is empty")?;
+
+        anyhow::bail!("unreachable")
+    })
+    .await;
+
+    assert!(report.outcome.is_passed());
+}
+
+#[tokio::test]
+async fn errors_reading_like_the_sentinel_fail() {
+    let report = Fixture::test_reported_with_context(|context| async move {
+        let early = context.complete_early::<()>().unwrap_err();
+        let message = early.to_string();
+        drop(early);
+
+        anyhow::bail!(message)
+    })
+    .await;
+
+    match report.outcome {
+        TestOutcome::Failed(error) => assert!(error.to_string().contains("completed early")),
+        outcome => panic!("unexpected outcome {:?}", outcome),
+    }
+}
+
+/// Custom error of a fixture that doesn't erase its errors like `anyhow` does
+#[derive(Debug)]
+enum DatasetError {
+    CompletedEarly(CompletedEarly),
+    Empty,
+}
+
+impl From<CompletedEarly> for DatasetError {
+    fn from(early: CompletedEarly) -> Self {
+        Self::CompletedEarly(early)
+    }
+}
+
+impl fmt::Display for DatasetError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CompletedEarly(early) => write!(formatter, "{}", early),
+            Self::Empty => write!(formatter, "dataset is empty"),
+        }
+    }
+}
+
+struct Dataset;
+
+#[spekt::async_trait]
+impl Test for Dataset {
+    type Error = DatasetError;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[tokio::test]
+async fn completing_early_through_custom_errors_passes() {
+    let report = Dataset::test_reported_with_context(|context| async move {
+        context.complete_early()?;
+
+        Err(DatasetError::Empty)
+    })
+    .await;
+
+    assert!(report.outcome.is_passed());
+
+    let report = Dataset::test_reported_with_context(|context| async move {
+        let _ = context.complete_early::<()>();
+
+        Err(DatasetError::Empty)
+    })
+    .await;
+
+    assert!(matches!(
+        report.outcome,
+        TestOutcome::Failed(DatasetError::Empty)
+    ));
+}
@@ -0,0 +1,156 @@
+use spekt::{ErrInto, IntoTestError, Test, TestResult};
+use std::{
+    error::Error,
+    fmt,
+    io::{self, Read},
+};
+
+#[derive(Debug, PartialEq)]
+struct ConnectError;
+
+#[derive(Debug, PartialEq)]
+struct QueryError;
+
+/// Unified error of a fixture whose setup and task fail with different error types
+#[derive(Debug, PartialEq)]
+enum ClientError {
+    Connect(ConnectError),
+    Query(QueryError),
+}
+
+impl From<ConnectError> for ClientError {
+    fn from(error: ConnectError) -> Self {
+        Self::Connect(error)
+    }
+}
+
+impl From<QueryError> for ClientError {
+    fn from(error: QueryError) -> Self {
+        Self::Query(error)
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connect(_) => write!(formatter, "failed to connect"),
+            Self::Query(_) => write!(formatter, "failed to query"),
+        }
+    }
+}
+
+struct Client;
+
+impl Client {
+    async fn query(&self) -> Result<(), QueryError> {
+        Err(QueryError)
+    }
+}
+
+#[spekt::async_trait]
+impl Test for Client {
+    type Error = ClientError;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Client)
+    }
+}
+
+struct Offline;
+
+#[spekt::async_trait]
+impl Test for Offline {
+    type Error = ClientError;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Err::<Self, _>(ConnectError).err_into()
+    }
+}
+
+#[test]
+fn err_into_converts_through_from() {
+    assert_eq!(Ok::<_, QueryError>(1).err_into::<ClientError>(), Ok(1));
+    assert_eq!(
+        Err::<(), _>(ConnectError).err_into::<ClientError>(),
+        Err(ClientError::Connect(ConnectError))
+    );
+}
+
+#[tokio::test]
+async fn task_errors_unify_through_err_into() {
+    let error = Client::try_test(|client| async move { client.query().await.err_into() })
+        .await
+        .unwrap_err();
+
+    assert_eq!(error, ClientError::Query(QueryError));
+}
+
+#[tokio::test]
+async fn before_errors_unify_through_err_into() {
+    let error = Offline::try_test(|_| async { Ok(()) }).await.unwrap_err();
+
+    assert_eq!(error, ClientError::Connect(ConnectError));
+}
+
+struct Config;
+
+#[spekt::async_trait]
+impl Test for Config {
+    type Error = Box<dyn Error + Send + Sync>;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+fn read_config(config: &str) -> io::Result<String> {
+    let mut contents = String::new();
+
+    io::Cursor::new(config).read_to_string(&mut contents)?;
+
+    Ok(contents)
+}
+
+fn missing_config() -> io::Result<String> {
+    Err(io::Error::new(io::ErrorKind::NotFound, "missing config"))
+}
+
+#[tokio::test]
+async fn io_errors_flow_into_test_results() {
+    Config::test(|_| async move {
+        let config = read_config("debug = true")?;
+        assert!(config.contains("debug"));
+
+        TestResult::Ok(())
+    })
+    .await;
+
+    let error = Config::try_test(|_| async move {
+        missing_config()?;
+
+        TestResult::Ok(())
+    })
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        error.downcast::<io::Error>().unwrap().kind(),
+        io::ErrorKind::NotFound
+    );
+}
+
+#[tokio::test]
+async fn errors_out_of_reach_of_question_marks_convert_into_test_errors() {
+    let error = Client::try_test(|_| async move {
+        let results: Vec<Result<(), QueryError>> = vec![Ok(()), Err(QueryError)];
+
+        match results.into_iter().find_map(Result::err) {
+            Some(error) => Err(error.into_test_error()),
+            None => Ok(()),
+        }
+    })
+    .await
+    .unwrap_err();
+
+    assert_eq!(error, ClientError::Query(QueryError));
+}
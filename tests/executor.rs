@@ -0,0 +1,101 @@
+use spekt::{EnterError, Test};
+use std::panic;
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+const NESTED: &str =
+    "cannot block on a test from inside a running executor, await the test instead";
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().unwrap().to_string(),
+    }
+}
+
+#[test]
+fn blocking_on_a_test_runs_it() {
+    let mut ran = false;
+
+    Fixture::test_blocking(|_| async {
+        ran = true;
+
+        Ok(())
+    });
+
+    assert!(ran);
+}
+
+#[test]
+fn nested_blocking_fails_instead_of_deadlocking() {
+    let payload = panic::catch_unwind(|| {
+        Fixture::test_blocking(|_| async {
+            Fixture::test_blocking(|_| async { Ok(()) });
+
+            Ok(())
+        })
+    })
+    .unwrap_err();
+
+    assert!(panic_message(payload).contains(NESTED));
+}
+
+#[test]
+fn nested_blocking_returns_an_enter_error() {
+    let mut ran = false;
+    let error = Fixture::try_test_blocking(|_| async {
+        Fixture::try_test_blocking(|_| async {
+            ran = true;
+
+            Ok(())
+        })
+    })
+    .unwrap_err();
+
+    assert!(!ran);
+    assert_eq!(error.downcast::<EnterError>().unwrap(), EnterError);
+    assert_eq!(EnterError.to_string(), NESTED);
+}
+
+#[test]
+fn blocking_errors_are_returned() {
+    let error = Fixture::try_test_blocking(|_| async { anyhow::bail!("no rows") }).unwrap_err();
+
+    assert_eq!(error.to_string(), "no rows");
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn blocking_inside_a_tokio_runtime_fails_instead_of_hanging() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let payload = panic::catch_unwind(|| {
+        runtime.block_on(async { Fixture::test_blocking(|_| async { Ok(()) }) })
+    })
+    .unwrap_err();
+
+    assert_eq!(panic_message(payload), NESTED);
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn blocking_inside_a_tokio_runtime_returns_an_enter_error() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let error = runtime
+        .block_on(async { Fixture::try_test_blocking(|_| async { Ok(()) }) })
+        .unwrap_err();
+
+    assert!(error.is::<EnterError>());
+}
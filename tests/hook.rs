@@ -0,0 +1,76 @@
+use spekt::{Test, TestConfig, TestOutcome};
+use std::{
+    panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Barrier,
+    },
+    thread,
+};
+
+static PRINTED: AtomicUsize = AtomicUsize::new(0);
+
+struct Worker;
+
+#[spekt::async_trait]
+impl Test for Worker {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[test]
+fn silencing_only_affects_the_silenced_run() {
+    panic::set_hook(Box::new(|_| {
+        PRINTED.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let config = TestConfig::builder().suppress_panic_output(true).build();
+    let barrier = Arc::new(Barrier::new(2));
+    let silenced = thread::spawn({
+        let barrier = Arc::clone(&barrier);
+        let config = config.clone();
+
+        move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .build()
+                .unwrap();
+
+            runtime.block_on(Worker::test_with_config(config, |_| {
+                let barrier = Arc::clone(&barrier);
+
+                async move {
+                    barrier.wait();
+                    assert!(panic::catch_unwind(|| panic!("expected")).is_err());
+                    barrier.wait();
+
+                    Ok(())
+                }
+            }))
+        }
+    });
+
+    barrier.wait();
+    let _ = panic::catch_unwind(|| panic!("unrelated"));
+    barrier.wait();
+
+    silenced.join().unwrap();
+
+    assert_eq!(PRINTED.load(Ordering::SeqCst), 1);
+
+    let outcome = runtime.block_on(Worker::run_with_config(config, |_| async {
+        panic!("expected")
+    }));
+
+    match outcome {
+        TestOutcome::Panicked(message) => assert_eq!(message, "expected"),
+        outcome => panic!("unexpected outcome {:?}", outcome),
+    }
+
+    assert_eq!(PRINTED.load(Ordering::SeqCst), 1);
+}
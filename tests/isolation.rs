@@ -0,0 +1,288 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::{BeforeOutcome, CleanupStack, Isolation, Resources, Test, TestConfig};
+use std::{
+    future,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+static BUILDS: AtomicUsize = AtomicUsize::new(0);
+static TEARDOWNS: AtomicUsize = AtomicUsize::new(0);
+
+struct Database;
+
+#[spekt::async_trait]
+impl Test for Database {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        BUILDS.fetch_add(1, Ordering::SeqCst);
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        TEARDOWNS.fetch_add(1, Ordering::SeqCst);
+
+        Ok(())
+    }
+}
+
+fn counts() -> (usize, usize) {
+    (
+        BUILDS.swap(0, Ordering::SeqCst),
+        TEARDOWNS.swap(0, Ordering::SeqCst),
+    )
+}
+
+async fn run(isolation: Isolation) {
+    let config = TestConfig::builder().isolation(isolation).build();
+    let task = |_| async { Ok(()) };
+
+    Database::test_isolated(config, vec![task, task, task]).await;
+}
+
+// every isolation level shares the same counters, so they're checked in a single test
+#[tokio::test]
+async fn isolation_levels_build_and_tear_down_state() {
+    run(Isolation::Fresh).await;
+    assert_eq!(counts(), (3, 3));
+
+    run(Isolation::Shared).await;
+    assert_eq!(counts(), (1, 1));
+
+    let task = |_| async { Ok(()) };
+
+    Database::test_shared(vec![task, task]).await;
+    assert_eq!(counts(), (1, 1));
+
+    run(Isolation::Persistent).await;
+    run(Isolation::Persistent).await;
+    assert_eq!(counts(), (1, 0));
+
+    Database::release_persistent().await.unwrap();
+    assert_eq!(counts(), (0, 1));
+
+    Database::release_persistent().await.unwrap();
+    assert_eq!(counts(), (0, 0));
+
+    run(Isolation::Persistent).await;
+    Database::release_persistent().await.unwrap();
+    assert_eq!(counts(), (1, 1));
+}
+
+struct Broken;
+
+#[spekt::async_trait]
+impl Test for Broken {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Err(anyhow::anyhow!("no database"))
+    }
+}
+
+#[tokio::test]
+async fn failing_persistent_setup_is_reported_and_retried() {
+    let config = TestConfig::builder()
+        .isolation(Isolation::Persistent)
+        .build();
+
+    for _ in 0..2 {
+        let task = |_| async { Ok(()) };
+        let message = panic_message(Broken::test_isolated(config.clone(), vec![task])).await;
+
+        assert_eq!(message, "no database");
+    }
+}
+
+struct Flaky;
+
+#[spekt::async_trait]
+impl Test for Flaky {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[tokio::test]
+async fn shared_tasks_report_every_failure() {
+    let config = TestConfig::builder().isolation(Isolation::Shared).build();
+    let task = |_: Arc<Flaky>| async { Err(anyhow::anyhow!("broken")) };
+    let message = panic_message(Flaky::test_isolated(config, vec![task, task])).await;
+
+    assert_eq!(message, "task 0 failed: broken\ntask 1 failed: broken");
+}
+
+static FAST_BUILT: AtomicBool = AtomicBool::new(false);
+
+struct Slow;
+
+#[spekt::async_trait]
+impl Test for Slow {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        while !FAST_BUILT.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        Ok(Self)
+    }
+}
+
+struct Fast;
+
+#[spekt::async_trait]
+impl Test for Fast {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        FAST_BUILT.store(true, Ordering::SeqCst);
+
+        Ok(Self)
+    }
+}
+
+#[tokio::test]
+async fn persistent_states_of_different_types_build_concurrently() {
+    let config = TestConfig::builder()
+        .isolation(Isolation::Persistent)
+        .build();
+    let slow = Slow::test_isolated(config.clone(), vec![|_| async { Ok(()) }]);
+    let fast = async {
+        tokio::task::yield_now().await;
+        Fast::test_isolated(config.clone(), vec![|_| async { Ok(()) }]).await
+    };
+
+    tokio::time::timeout(Duration::from_secs(5), async { tokio::join!(slow, fast) })
+        .await
+        .expect("building one persistent state held up another");
+}
+
+struct Hanging;
+
+#[spekt::async_trait]
+impl Test for Hanging {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[tokio::test]
+async fn shared_and_persistent_tasks_are_each_held_to_the_test_deadline() {
+    for isolation in [Isolation::Shared, Isolation::Persistent] {
+        let config = TestConfig::builder()
+            .isolation(isolation)
+            .test_timeout(Duration::from_millis(10))
+            .build();
+        let hang = |_: Arc<Hanging>| async {
+            future::pending::<()>().await;
+
+            Ok(())
+        };
+        let message = panic_message(Hanging::test_isolated(config, vec![hang, hang])).await;
+
+        assert_eq!(
+            message,
+            "task 0 failed: test() timed out after 10ms\ntask 1 failed: test() timed out after 10ms"
+        );
+    }
+
+    Hanging::release_persistent().await.unwrap();
+}
+
+struct Pooled;
+
+#[spekt::async_trait]
+impl Test for Pooled {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        unreachable!("built through before_with_cleanup")
+    }
+
+    async fn before_with_cleanup(
+        cleanup: &mut CleanupStack<Self::Error>,
+    ) -> Result<Self, Self::Error> {
+        record("before");
+        cleanup.defer(|| async {
+            record("cleanup");
+
+            Ok(())
+        });
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn persistent_states_are_built_and_released_through_setup() {
+    let config = TestConfig::builder()
+        .isolation(Isolation::Persistent)
+        .build();
+    let task = |_| async {
+        record("task");
+
+        Ok(())
+    };
+
+    Pooled::test_isolated(config.clone(), vec![task]).await;
+    Pooled::test_isolated(config, vec![task]).await;
+    Pooled::release_persistent().await.unwrap();
+
+    assert_eq!(events(), ["before", "task", "task", "after", "cleanup"]);
+}
+
+struct Unavailable;
+
+#[spekt::async_trait]
+impl Test for Unavailable {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        unreachable!("built through before_or_skip")
+    }
+
+    async fn before_or_skip(
+        _: &mut CleanupStack<Self::Error>,
+        _: &mut Resources,
+    ) -> Result<BeforeOutcome<Self>, Self::Error> {
+        record("skip");
+
+        Ok(BeforeOutcome::Skip("no database".into()))
+    }
+}
+
+#[tokio::test]
+async fn skipped_persistent_setup_runs_no_tasks() {
+    let config = TestConfig::builder()
+        .isolation(Isolation::Persistent)
+        .build();
+    let task = |_| async {
+        record("task");
+
+        Ok(())
+    };
+
+    Unavailable::test_isolated(config.clone(), vec![task]).await;
+    Unavailable::test_isolated(config, vec![task]).await;
+    Unavailable::release_persistent().await.unwrap();
+
+    assert_eq!(events(), ["skip", "skip"]);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "local")]
+
+use spekt::{LocalTest, Shared};
+use std::{cell::RefCell, ops::Deref, rc::Rc, sync::Arc};
+
+struct Session {
+    history: RefCell<Vec<&'static str>>,
+}
+
+#[spekt::async_trait(?Send)]
+impl LocalTest for Session {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self {
+            history: RefCell::new(Vec::new()),
+        })
+    }
+}
+
+/// Custom handle around an `Rc`
+struct Tracked<T>(Rc<T>);
+
+impl<T> Clone for Tracked<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> Shared<T> for Tracked<T> {
+    fn share(state: T) -> Self {
+        Self(Rc::new(state))
+    }
+}
+
+#[tokio::test]
+async fn tasks_receive_an_rc_by_default() {
+    Session::test(|session: Rc<Session>| async move {
+        session.history.borrow_mut().push("login");
+
+        Ok(())
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn tasks_receive_the_handle_they_ask_for() {
+    Session::test_with(|session: Arc<Session>| async move {
+        session.history.borrow_mut().push("login");
+
+        Ok(())
+    })
+    .await;
+
+    Session::test_with(|session: Tracked<Session>| async move {
+        session.history.borrow_mut().push("login");
+        anyhow::ensure!(Rc::strong_count(&session.0) == 2, "expected two handles");
+
+        Ok(())
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn failures_surface_through_any_handle() {
+    let error =
+        Session::try_test_with(|_: Tracked<Session>| async { Err(anyhow::anyhow!("logged out")) })
+            .await
+            .unwrap_err();
+
+    assert_eq!(error.to_string(), "logged out");
+}
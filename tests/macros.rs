@@ -0,0 +1,55 @@
+#![cfg(feature = "macros")]
+
+use spekt::Test;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[spekt::test]
+async fn runs_the_body(_fixture: Arc<Fixture>) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+#[spekt::test]
+#[should_panic(expected = "broken")]
+async fn fails_with_the_body(_fixture: Arc<Fixture>) -> Result<(), anyhow::Error> {
+    Err(anyhow::anyhow!("broken"))
+}
+
+#[spekt::test(retries = 2)]
+async fn retries_the_body(_fixture: Arc<Fixture>) -> Result<(), anyhow::Error> {
+    match ATTEMPTS.fetch_add(1, Ordering::SeqCst) {
+        0 | 1 => Err(anyhow::anyhow!("flaky")),
+        _ => Ok(()),
+    }
+}
+
+#[spekt::test(timeout = "10ms")]
+#[should_panic(expected = "timed out")]
+async fn times_out_the_body(_fixture: Arc<Fixture>) -> Result<(), anyhow::Error> {
+    spekt::sleep(std::time::Duration::from_secs(5)).await;
+
+    Ok(())
+}
+
+#[spekt::test(timeout = "10ms", retries = 1)]
+#[should_panic(expected = "timed out")]
+async fn times_out_every_attempt(_fixture: Arc<Fixture>) -> Result<(), anyhow::Error> {
+    spekt::sleep(std::time::Duration::from_secs(5)).await;
+
+    Ok(())
+}
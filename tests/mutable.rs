@@ -0,0 +1,99 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::Test;
+
+struct Counter {
+    count: usize,
+}
+
+#[spekt::async_trait]
+impl Test for Counter {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self { count: 0 })
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record(format!("after {}", self.count));
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn after_sees_mutations_made_by_the_task() {
+    Counter::test_mut(|counter| {
+        Box::pin(async move {
+            counter.count += 2;
+
+            Ok(())
+        })
+    })
+    .await;
+
+    assert_eq!(events(), ["after 2"]);
+}
+
+#[tokio::test]
+async fn failing_tasks_are_torn_down_with_their_mutations() {
+    let message = panic_message(Counter::test_mut(|counter| {
+        Box::pin(async move {
+            counter.count += 1;
+            anyhow::ensure!(counter.count == 2, "count is {}", counter.count);
+
+            Ok(())
+        })
+    }))
+    .await;
+
+    assert_eq!(message, "count is 1");
+    assert_eq!(events(), ["after 1"]);
+}
+
+#[tokio::test]
+async fn panicking_tasks_are_torn_down_with_their_mutations() {
+    let message = panic_message(Counter::test_mut(|counter| {
+        Box::pin(async move {
+            counter.count += 3;
+            panic!("poisoned counter")
+        })
+    }))
+    .await;
+
+    assert_eq!(message, "poisoned counter");
+    assert_eq!(events(), ["after 3"]);
+}
+
+struct Broken;
+
+#[spekt::async_trait]
+impl Test for Broken {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        anyhow::bail!("no counter")
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn failing_setup_never_runs_the_task() {
+    let message = panic_message(Broken::test_mut(|_| {
+        Box::pin(async {
+            record("task");
+
+            Ok(())
+        })
+    }))
+    .await;
+
+    assert_eq!(message, "no counter");
+    assert!(events().is_empty());
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::{Test, TestOutcome};
+use std::time::Duration;
+
+struct Recorder;
+
+#[spekt::async_trait]
+impl Test for Recorder {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+
+    async fn after_outcome(&self, outcome: &TestOutcome<Self::Error>) -> Result<(), Self::Error> {
+        match outcome {
+            TestOutcome::Passed => record("passed"),
+            TestOutcome::Failed(error) => record(format!("failed: {}", error)),
+            TestOutcome::TimedOut(duration) => record(format!("timed out: {:?}", duration)),
+            TestOutcome::Panicked(message) => record(format!("panicked: {}", message)),
+            outcome => record(format!("{:?}", outcome)),
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn after_outcome_sees_passing_tasks() {
+    Recorder::test(|_| async { Ok(()) }).await;
+
+    assert_eq!(events(), ["passed"]);
+}
+
+#[tokio::test]
+async fn after_outcome_sees_the_original_error_of_failing_tasks() {
+    let message = panic_message(Recorder::test(|_| async {
+        anyhow::bail!("disk full");
+    }))
+    .await;
+
+    assert_eq!(message, "disk full");
+    assert_eq!(events(), ["failed: disk full"]);
+}
+
+#[tokio::test]
+async fn after_outcome_sees_the_message_of_panicking_tasks() {
+    let message = panic_message(Recorder::test(|_| async {
+        panic!("index out of bounds");
+    }))
+    .await;
+
+    assert_eq!(message, "index out of bounds");
+    assert_eq!(events(), ["panicked: index out of bounds"]);
+}
+
+#[tokio::test]
+async fn after_outcome_sees_the_deadline_of_timed_out_tasks() {
+    let message = panic_message(Recorder::test_with_timeout(
+        Duration::from_millis(10),
+        |_| async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+
+            Ok(())
+        },
+    ))
+    .await;
+
+    assert_eq!(message, "test() timed out after 10ms");
+    assert_eq!(events(), ["timed out: 10ms"]);
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::{BeforeOutcome, Chain, CleanupStack, Resources, Test, TestOutcome};
+use std::any::Any;
+
+struct Table;
+
+#[spekt::async_trait]
+impl Test for Table {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+
+    async fn after_with<R>(
+        &self,
+        output: Option<&R>,
+        outcome: &TestOutcome<Self::Error>,
+    ) -> Result<(), Self::Error>
+    where
+        R: Any + Send + Sync,
+    {
+        let row = output.and_then(|output| (output as &dyn Any).downcast_ref::<u64>());
+
+        match (row, outcome) {
+            (Some(row), _) => record(format!("delete row {}", row)),
+            (None, TestOutcome::Failed(error)) => record(format!("truncate after {}", error)),
+            (None, TestOutcome::Panicked(message)) => record(format!("truncate after {}", message)),
+            (None, outcome) => record(format!("truncate after {:?}", outcome)),
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn passing_tasks_hand_their_output_to_after_with() {
+    let row = Table::test_returning(|_| async { Ok(42_u64) }).await;
+
+    assert_eq!(row, Some(42));
+    assert_eq!(events(), ["delete row 42"]);
+}
+
+#[tokio::test]
+async fn failing_tasks_leave_after_with_without_an_output() {
+    let message = panic_message(Table::test_returning(|_| async {
+        anyhow::bail!("duplicate key");
+
+        #[allow(unreachable_code)]
+        Ok(42_u64)
+    }))
+    .await;
+
+    assert_eq!(message, "duplicate key");
+    assert_eq!(events(), ["truncate after duplicate key"]);
+}
+
+#[tokio::test]
+async fn panicking_tasks_leave_after_with_without_an_output() {
+    let message = panic_message(Table::test_returning(|_| async {
+        if true {
+            panic!("connection dropped");
+        }
+
+        Ok(42_u64)
+    }))
+    .await;
+
+    assert_eq!(message, "connection dropped");
+    assert_eq!(events(), ["truncate after connection dropped"]);
+}
+
+/// Fixture whose prerequisites are never met, so that every test using it is skipped
+struct Offline;
+
+#[spekt::async_trait]
+impl Test for Offline {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        unreachable!("built through before_or_skip")
+    }
+
+    async fn before_or_skip(
+        _: &mut CleanupStack<Self::Error>,
+        _: &mut Resources,
+    ) -> Result<BeforeOutcome<Self>, Self::Error> {
+        record("skip");
+
+        Ok(BeforeOutcome::Skip("database is offline".into()))
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn skipped_tasks_return_no_output() {
+    let row = Offline::test_returning(|_| async {
+        record("task");
+
+        Ok(42_u64)
+    })
+    .await;
+
+    assert_eq!(row, None);
+    assert_eq!(events(), ["skip"]);
+}
+
+#[tokio::test]
+async fn skipped_tasks_hand_back_no_state() {
+    let state = Offline::try_test_into_state(|_| async {
+        record("task");
+
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    assert!(state.is_none());
+    assert_eq!(events(), ["skip"]);
+}
+
+#[tokio::test]
+async fn skipped_chains_return_no_output() {
+    let name = Chain::<Offline, _>::new()
+        .then(|_, ()| async {
+            record("step");
+
+            Ok("alice")
+        })
+        .try_test()
+        .await
+        .unwrap();
+
+    assert_eq!(name, None);
+    assert_eq!(events(), ["skip"]);
+}
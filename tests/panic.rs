@@ -0,0 +1,132 @@
+mod common;
+
+use common::{events, message_of, panic_message, record};
+use spekt::{CleanupStack, Test, TestConfig};
+use std::{panic, time::Duration};
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        record("before");
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn after_runs_before_a_task_panic_is_re_raised() {
+    let message = panic_message(Fixture::test(|_| async {
+        record("task");
+        panic!("stray unwrap");
+    }))
+    .await;
+
+    assert_eq!(message, "stray unwrap");
+    assert_eq!(events(), ["before", "task", "after"]);
+}
+
+#[test]
+fn after_runs_before_a_blocking_task_panic_is_re_raised() {
+    let payload = panic::catch_unwind(|| {
+        Fixture::test_blocking(|_| async {
+            assert_eq!(1 + 1, 3, "bad arithmetic");
+
+            Ok(())
+        })
+    })
+    .unwrap_err();
+
+    assert!(message_of(&*payload).contains("bad arithmetic"));
+    assert_eq!(events(), ["before", "after"]);
+}
+
+#[tokio::test]
+async fn panics_are_not_mistaken_for_timeouts() {
+    let config = TestConfig::builder()
+        .test_timeout(Duration::from_secs(5))
+        .build();
+    let message = panic_message(Fixture::test_with_config(config, |_| async {
+        panic!("connection reset")
+    }))
+    .await;
+
+    assert_eq!(message, "connection reset");
+    assert_eq!(events(), ["before", "after"]);
+}
+
+struct Failing;
+
+#[spekt::async_trait]
+impl Test for Failing {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        anyhow::bail!("teardown failed")
+    }
+}
+
+#[tokio::test]
+async fn task_panics_win_over_teardown_failures() {
+    let message = panic_message(Failing::test(|_| async { panic!("stray unwrap") })).await;
+
+    assert_eq!(message, "stray unwrap");
+    assert_eq!(events(), ["after"]);
+}
+
+struct Crashing;
+
+#[spekt::async_trait]
+impl Test for Crashing {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        unreachable!("built through before_with_cleanup")
+    }
+
+    async fn before_with_cleanup(
+        cleanup: &mut CleanupStack<Self::Error>,
+    ) -> Result<Self, Self::Error> {
+        cleanup.defer(|| async {
+            record("cleanup");
+
+            Ok(())
+        });
+
+        panic!("setup crashed")
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn setup_panics_run_registered_clean_up_but_not_after() {
+    let message = panic_message(Crashing::test(|_| async {
+        record("task");
+
+        Ok(())
+    }))
+    .await;
+
+    assert_eq!(message, "setup crashed");
+    assert_eq!(events(), ["cleanup"]);
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::{BoxFuture, FailureMode, Test, TestConfig};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const LATENCY: Duration = Duration::from_millis(50);
+
+struct Worker;
+
+#[spekt::async_trait]
+impl Test for Worker {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        record("before");
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+type Task = fn(Arc<Worker>) -> BoxFuture<'static, anyhow::Result<()>>;
+
+fn slow(_: Arc<Worker>) -> BoxFuture<'static, anyhow::Result<()>> {
+    Box::pin(async {
+        tokio::time::sleep(LATENCY).await;
+
+        Ok(())
+    })
+}
+
+fn passing(_: Arc<Worker>) -> BoxFuture<'static, anyhow::Result<()>> {
+    Box::pin(async { Ok(()) })
+}
+
+fn failing(_: Arc<Worker>) -> BoxFuture<'static, anyhow::Result<()>> {
+    Box::pin(async { anyhow::bail!("job rejected") })
+}
+
+fn panicking(_: Arc<Worker>) -> BoxFuture<'static, anyhow::Result<()>> {
+    Box::pin(async { panic!("queue poisoned") })
+}
+
+#[tokio::test]
+async fn tasks_run_concurrently() {
+    let started = Instant::now();
+
+    Worker::test_parallel(vec![slow as Task, slow, slow], 3).await;
+
+    let elapsed = started.elapsed();
+
+    assert!(elapsed >= LATENCY, "{:?}", elapsed);
+    assert!(elapsed < LATENCY * 3, "{:?}", elapsed);
+    assert_eq!(
+        events(),
+        ["before", "before", "before", "after", "after", "after"]
+    );
+}
+
+#[tokio::test]
+async fn every_failing_task_is_reported_by_index() {
+    let message = panic_message(Worker::test_parallel(
+        vec![failing as Task, passing, failing],
+        2,
+    ))
+    .await;
+
+    assert_eq!(
+        message,
+        "task 0 failed: job rejected\ntask 2 failed: job rejected"
+    );
+    assert_eq!(events().iter().filter(|event| *event == "after").count(), 3);
+}
+
+#[tokio::test]
+async fn panicking_tasks_are_reported_without_stopping_the_others() {
+    let message = panic_message(Worker::test_parallel(vec![panicking as Task, passing], 2)).await;
+
+    assert_eq!(message, "task 0 failed: panicked: queue poisoned");
+    assert_eq!(events().iter().filter(|event| *event == "after").count(), 2);
+}
+
+#[tokio::test]
+async fn timed_out_tasks_are_reported_with_their_deadline() {
+    let config = TestConfig::builder()
+        .test_timeout(Duration::from_millis(10))
+        .build();
+    let message = panic_message(Worker::test_parallel_with_config(
+        config,
+        vec![slow as Task, passing],
+        2,
+    ))
+    .await;
+
+    assert_eq!(message, "task 0 failed: test() timed out after 10ms");
+}
+
+#[tokio::test]
+async fn fail_fast_skips_the_tasks_left_after_a_failure() {
+    let config = TestConfig::builder()
+        .failure_mode(FailureMode::FailFast)
+        .build();
+    let message = panic_message(Worker::test_parallel_with_config(
+        config,
+        vec![failing as Task, passing, passing],
+        1,
+    ))
+    .await;
+
+    assert_eq!(
+        message,
+        "task 0 failed: job rejected\ntask 1 skipped\ntask 2 skipped"
+    );
+    assert_eq!(events(), ["before", "after"]);
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::{FailureMode, Test, TestConfig};
+use std::time::Duration;
+
+struct Calculator;
+
+#[spekt::async_trait]
+impl Test for Calculator {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        record("before");
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn every_case_gets_its_own_lifecycle() {
+    Calculator::test_each([1, 2, 3], |_, number| async move {
+        record(format!("case {}", number));
+
+        Ok(())
+    })
+    .await;
+
+    assert_eq!(
+        events(),
+        ["before", "case 1", "after", "before", "case 2", "after", "before", "case 3", "after"]
+    );
+}
+
+#[tokio::test]
+async fn failing_cases_name_their_parameters() {
+    let message = panic_message(Calculator::test_each(
+        ["one", "two", "three"],
+        |_, word| async move {
+            anyhow::ensure!(word.len() == 3, "{} has {} letters", word, word.len());
+
+            Ok(())
+        },
+    ))
+    .await;
+
+    assert_eq!(message, "case \"three\" failed: three has 5 letters");
+}
+
+#[tokio::test]
+async fn panicking_cases_are_reported_without_stopping_the_table() {
+    let message = panic_message(Calculator::test_each([1, 0, 2], |_, divisor| async move {
+        record(format!("{}", 10 / divisor));
+
+        Ok(())
+    }))
+    .await;
+
+    assert_eq!(
+        message,
+        "case 0 failed: panicked: attempt to divide by zero"
+    );
+    assert_eq!(
+        events(),
+        ["before", "10", "after", "before", "after", "before", "5", "after"]
+    );
+}
+
+#[tokio::test]
+async fn fail_fast_stops_at_a_panicking_case() {
+    let config = TestConfig::builder()
+        .failure_mode(FailureMode::FailFast)
+        .build();
+    let message = panic_message(Calculator::test_each_with_config(
+        config,
+        [0, 1],
+        |_, divisor| async move {
+            record(format!("{}", 10 / divisor));
+
+            Ok(())
+        },
+    ))
+    .await;
+
+    assert_eq!(
+        message,
+        "case 0 failed: panicked: attempt to divide by zero\ncase 1 skipped"
+    );
+}
+
+#[tokio::test]
+async fn timed_out_cases_are_reported() {
+    let config = TestConfig::builder()
+        .test_timeout(Duration::from_millis(10))
+        .build();
+    let message = panic_message(Calculator::test_each_with_config(
+        config,
+        [Duration::ZERO, Duration::from_secs(5)],
+        |_, delay| async move {
+            spekt::sleep(delay).await;
+
+            Ok(())
+        },
+    ))
+    .await;
+
+    assert_eq!(message, "case 5s failed: test() timed out after 10ms");
+}
+
+#[tokio::test]
+async fn cases_with_context_report_failures_and_panics() {
+    let message = panic_message(Calculator::test_each_with_context(
+        [1, 0, 2],
+        |context, divisor| async move {
+            anyhow::ensure!(
+                context.metadata().param_debug.as_deref() == Some("1") || divisor != 1,
+                "missing parameter"
+            );
+            anyhow::ensure!(divisor != 2, "{} is even", divisor);
+            record(format!("{}", 10 / divisor));
+
+            Ok(())
+        },
+    ))
+    .await;
+
+    assert_eq!(
+        message,
+        "case 0 failed: panicked: attempt to divide by zero\ncase 2 failed: 2 is even"
+    );
+}
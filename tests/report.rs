@@ -0,0 +1,119 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::{Test, TestOutcome};
+use std::{cell::Cell, time::Duration};
+
+const PHASE: Duration = Duration::from_millis(20);
+
+thread_local! {
+    static FAILING: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Make the given phase of the next `Cache` fail
+fn fail_in(phase: &'static str) {
+    FAILING.with(|failing| failing.set(Some(phase)));
+}
+
+fn failing(phase: &str) -> bool {
+    FAILING.with(|failing| failing.get() == Some(phase))
+}
+
+struct Cache;
+
+#[spekt::async_trait]
+impl Test for Cache {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        record("before");
+        tokio::time::sleep(PHASE).await;
+        anyhow::ensure!(!failing("before"), "cache is unreachable");
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+        tokio::time::sleep(PHASE).await;
+        anyhow::ensure!(!failing("after"), "cache refused to flush");
+
+        Ok(())
+    }
+}
+
+/// Display the failure of an outcome, leaving its error's Debug representation out
+fn describe(outcome: &TestOutcome<anyhow::Error>) -> String {
+    match outcome {
+        TestOutcome::Failed(error) => format!("failed: {}", error),
+        outcome => format!("{:?}", outcome),
+    }
+}
+
+#[tokio::test]
+async fn every_phase_is_timed() {
+    let report = Cache::test_reported(|_| async {
+        tokio::time::sleep(PHASE).await;
+
+        Ok(())
+    })
+    .await;
+
+    assert!(report.outcome.is_passed());
+    assert!(report.before_duration >= PHASE);
+    assert!(report.test_duration >= PHASE);
+    assert!(report.after_duration >= PHASE);
+    assert!(report.total_duration() >= PHASE * 3);
+}
+
+#[tokio::test]
+async fn failing_tasks_are_reported_with_their_error() {
+    let report = Cache::test_reported(|_| async {
+        anyhow::bail!("stale entry");
+    })
+    .await;
+
+    assert_eq!(describe(&report.outcome), "failed: stale entry");
+    assert!(report.after_duration >= PHASE);
+    assert_eq!(events(), ["before", "after"]);
+}
+
+#[tokio::test]
+async fn panicking_tasks_are_re_raised_once_after_has_run() {
+    let message = panic_message(Cache::test_reported(|_| async {
+        panic!("eviction underflow");
+    }))
+    .await;
+
+    assert_eq!(message, "eviction underflow");
+    assert_eq!(events(), ["before", "after"]);
+}
+
+#[tokio::test]
+async fn failing_before_is_reported_without_running_the_task() {
+    fail_in("before");
+
+    let report = Cache::test_reported(|_| async {
+        record("task");
+
+        Ok(())
+    })
+    .await;
+
+    assert_eq!(describe(&report.outcome), "failed: cache is unreachable");
+    assert!(report.before_duration >= PHASE);
+    assert_eq!(report.test_duration, Duration::ZERO);
+    assert_eq!(report.after_duration, Duration::ZERO);
+    assert_eq!(events(), ["before"]);
+}
+
+#[tokio::test]
+async fn failing_after_is_reported_once_the_task_passes() {
+    fail_in("after");
+
+    let report = Cache::test_reported(|_| async { Ok(()) }).await;
+
+    assert_eq!(describe(&report.outcome), "failed: cache refused to flush");
+    assert!(report.after_duration >= PHASE);
+    assert_eq!(events(), ["before", "after"]);
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::{Test, TestConfig};
+use std::{
+    cell::Cell,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+thread_local! {
+    static ATTEMPT: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Number of the attempt that's running, starting from 1
+fn attempt() -> usize {
+    ATTEMPT.with(|attempt| {
+        attempt.set(attempt.get() + 1);
+        attempt.get()
+    })
+}
+
+struct Service {
+    id: usize,
+}
+
+#[spekt::async_trait]
+impl Test for Service {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        static BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+        record("before");
+
+        Ok(Self {
+            id: BUILDS.fetch_add(1, Ordering::SeqCst),
+        })
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn flaky_tasks_pass_on_a_later_attempt_with_fresh_state() {
+    let ids = Mutex::new(Vec::new());
+
+    Service::test_with_retries(3, |service| {
+        let ids = &ids;
+
+        async move {
+            ids.lock().unwrap().push(service.id);
+            anyhow::ensure!(attempt() == 3, "connection refused");
+
+            Ok(())
+        }
+    })
+    .await;
+
+    let ids = ids.into_inner().unwrap();
+
+    assert_eq!(ids.len(), 3);
+    assert!(ids.windows(2).all(|pair| pair[0] != pair[1]));
+    assert_eq!(events(), ["before", "after"].repeat(3));
+}
+
+#[tokio::test]
+async fn exhausted_retries_fail_with_the_last_error() {
+    let message = panic_message(Service::test_with_retries(2, |_| async {
+        anyhow::bail!("attempt {} refused", attempt())
+    }))
+    .await;
+
+    assert_eq!(message, "attempt 2 refused");
+    assert_eq!(events(), ["before", "after"].repeat(2));
+}
+
+#[tokio::test]
+async fn panicking_attempts_are_torn_down_and_re_raised_without_retrying() {
+    let message = panic_message(Service::test_with_retries(3, |_| async {
+        attempt();
+        panic!("stray unwrap")
+    }))
+    .await;
+
+    assert_eq!(message, "stray unwrap");
+    assert_eq!(ATTEMPT.with(Cell::get), 1);
+    assert_eq!(events(), ["before", "after"]);
+}
+
+#[tokio::test]
+async fn timed_out_attempts_are_retried() {
+    let config = TestConfig::builder()
+        .test_timeout(Duration::from_millis(10))
+        .retries(2)
+        .build();
+
+    Service::test_with_config(config, |_| async {
+        if attempt() < 3 {
+            spekt::sleep(Duration::from_secs(5)).await;
+        }
+
+        Ok(())
+    })
+    .await;
+
+    assert_eq!(events(), ["before", "after"].repeat(3));
+}
+
+#[tokio::test]
+async fn attempts_that_keep_timing_out_fail_with_the_timeout() {
+    let config = TestConfig::builder()
+        .test_timeout(Duration::from_millis(10))
+        .retries(1)
+        .build();
+    let message = panic_message(Service::test_with_config(config, |_| async {
+        spekt::sleep(Duration::from_secs(5)).await;
+
+        Ok(())
+    }))
+    .await;
+
+    assert_eq!(message, "test() timed out after 10ms");
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::{BoxFuture, Test};
+use std::{cell::Cell, sync::Arc};
+
+thread_local! {
+    static BROKEN_BEFORE: Cell<bool> = const { Cell::new(false) };
+}
+
+struct Index;
+
+#[spekt::async_trait]
+impl Test for Index {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        record("before");
+        anyhow::ensure!(!BROKEN_BEFORE.with(Cell::get), "index is corrupt");
+
+        Ok(Self)
+    }
+
+    async fn before_each(&self) -> Result<(), Self::Error> {
+        record("before_each");
+
+        Ok(())
+    }
+
+    async fn after_each(&self) -> Result<(), Self::Error> {
+        record("after_each");
+
+        Ok(())
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+type Task = fn(Arc<Index>) -> BoxFuture<'static, anyhow::Result<()>>;
+
+fn passing(_: Arc<Index>) -> BoxFuture<'static, anyhow::Result<()>> {
+    Box::pin(async {
+        record("task");
+
+        Ok(())
+    })
+}
+
+fn failing(_: Arc<Index>) -> BoxFuture<'static, anyhow::Result<()>> {
+    Box::pin(async {
+        record("task");
+
+        anyhow::bail!("no match for \"spekt\"")
+    })
+}
+
+fn panicking(_: Arc<Index>) -> BoxFuture<'static, anyhow::Result<()>> {
+    Box::pin(async {
+        record("task");
+
+        panic!("posting list overflow")
+    })
+}
+
+#[tokio::test]
+async fn tasks_share_a_single_before_and_after() {
+    Index::test_shared(vec![passing as Task, passing]).await;
+
+    assert_eq!(
+        events(),
+        [
+            "before",
+            "before_each",
+            "task",
+            "after_each",
+            "before_each",
+            "task",
+            "after_each",
+            "after",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn every_failing_task_is_reported_by_index() {
+    let message = panic_message(Index::test_shared(vec![failing as Task, passing, failing])).await;
+
+    assert_eq!(
+        message,
+        "task 0 failed: no match for \"spekt\"\ntask 2 failed: no match for \"spekt\""
+    );
+
+    let events = events();
+
+    assert_eq!(events.iter().filter(|event| *event == "task").count(), 3);
+    assert_eq!(events.last().unwrap(), "after");
+}
+
+#[tokio::test]
+async fn panicking_tasks_leave_the_others_running() {
+    let message = panic_message(Index::test_shared(vec![panicking as Task, passing])).await;
+
+    assert_eq!(message, "task 0 failed: panicked: posting list overflow");
+    assert_eq!(
+        events(),
+        [
+            "before",
+            "before_each",
+            "task",
+            "after_each",
+            "before_each",
+            "task",
+            "after_each",
+            "after",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn failing_before_runs_no_tasks() {
+    BROKEN_BEFORE.with(|broken| broken.set(true));
+
+    let message = panic_message(Index::test_shared(vec![passing as Task, passing])).await;
+
+    assert_eq!(message, "index is corrupt");
+    assert_eq!(events(), ["before"]);
+}
@@ -0,0 +1,62 @@
+use spekt::{Test, TestOutcome};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static BUILDS: AtomicUsize = AtomicUsize::new(0);
+static TEARDOWNS: AtomicUsize = AtomicUsize::new(0);
+
+struct Service;
+
+#[spekt::async_trait]
+impl Test for Service {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        BUILDS.fetch_add(1, Ordering::SeqCst);
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        TEARDOWNS.fetch_add(1, Ordering::SeqCst);
+
+        Ok(())
+    }
+}
+
+fn counts() -> (usize, usize) {
+    (
+        BUILDS.swap(0, Ordering::SeqCst),
+        TEARDOWNS.swap(0, Ordering::SeqCst),
+    )
+}
+
+// every path shares the same counters, so they're checked in a single test
+#[tokio::test]
+async fn skip_if_reports_the_outcome_of_the_path_taken() {
+    let outcome = Service::test_skip_if(|| true, |_| async { panic!("ran a skipped task") }).await;
+
+    assert!(outcome.is_skipped());
+    assert_eq!(counts(), (0, 0));
+
+    let outcome = Service::test_skip_if(|| false, |_| async { Ok(()) }).await;
+
+    assert!(outcome.is_passed());
+    assert_eq!(counts(), (1, 1));
+
+    let outcome =
+        Service::test_skip_if(|| false, |_| async { Err(anyhow::anyhow!("unreachable")) }).await;
+
+    match outcome {
+        TestOutcome::Failed(error) => assert_eq!(error.to_string(), "unreachable"),
+        outcome => panic!("expected a failure, got {:?}", outcome),
+    }
+    assert_eq!(counts(), (1, 1));
+
+    let outcome = Service::test_skip_if(|| false, |_| async { panic!("connection refused") }).await;
+
+    match outcome {
+        TestOutcome::Panicked(message) => assert_eq!(message, "connection refused"),
+        outcome => panic!("expected a panic, got {:?}", outcome),
+    }
+    assert_eq!(counts(), (1, 1));
+}
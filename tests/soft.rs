@@ -0,0 +1,92 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::Test;
+
+struct Form;
+
+#[spekt::async_trait]
+impl Test for Form {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        record("before");
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn every_soft_failure_is_reported_together() {
+    let message = panic_message(Form::test_soft(|_, soft| async move {
+        soft.check_eq("name", "title");
+        soft.check("42x".parse::<u32>());
+        soft.fail("email is missing");
+
+        Ok(())
+    }))
+    .await;
+
+    assert_eq!(
+        message,
+        "3 soft assertions failed:\n  \
+         - assertion `left == right` failed: \"name\" != \"title\"\n  \
+         - invalid digit found in string\n  \
+         - email is missing"
+    );
+    assert_eq!(events(), ["before", "after"]);
+}
+
+#[tokio::test]
+async fn returned_errors_join_the_soft_failures() {
+    let message = panic_message(Form::test_soft(|_, soft| async move {
+        soft.fail("email is missing");
+        anyhow::bail!("submit rejected");
+    }))
+    .await;
+
+    assert_eq!(
+        message,
+        "2 soft assertions failed:\n  - email is missing\n  - submit rejected"
+    );
+    assert_eq!(events(), ["before", "after"]);
+}
+
+#[tokio::test]
+async fn passing_checks_hand_back_their_values() {
+    Form::test_soft(|_, soft| async move {
+        let age = soft.check("42".parse::<u32>());
+
+        assert_eq!(age, Some(42));
+        assert!(soft.check_eq(age, Some(42)));
+        assert!(soft.failures().is_empty());
+
+        Ok(())
+    })
+    .await;
+
+    assert_eq!(events(), ["before", "after"]);
+}
+
+#[tokio::test]
+async fn panicking_soft_tasks_still_tear_down() {
+    let message = panic_message(Form::test_soft(|_, soft| async move {
+        soft.fail("email is missing");
+
+        if true {
+            panic!("renderer crashed");
+        }
+
+        Ok(())
+    }))
+    .await;
+
+    assert_eq!(message, "renderer crashed");
+    assert_eq!(events(), ["before", "after"]);
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "tokio")]
+
+mod common;
+
+use common::{message_of, panic_message};
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    task::{Context, Waker},
+    thread,
+};
+
+#[tokio::test]
+async fn blocking_work_runs_off_the_runtime() {
+    let runtime_thread = thread::current().id();
+    let worker_thread = spekt::spawn_blocking(|| thread::current().id()).await;
+
+    assert_ne!(worker_thread, runtime_thread);
+}
+
+#[tokio::test]
+async fn blocking_panics_are_re_raised() {
+    let message = panic_message(spekt::spawn_blocking(|| panic!("driver crashed"))).await;
+
+    assert_eq!(message, "driver crashed");
+}
+
+#[test]
+fn blocking_work_cancelled_by_a_shutdown_panics_clearly() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let handle = runtime.handle().clone();
+    let mut context = Context::from_waker(Waker::noop());
+
+    runtime.shutdown_background();
+
+    // work spawned through a handle to a runtime that shut down is cancelled without running
+    let _entered = handle.enter();
+    let mut work = Box::pin(spekt::spawn_blocking(|| {
+        panic!("ran on a runtime that shut down")
+    }));
+    let payload =
+        panic::catch_unwind(AssertUnwindSafe(|| work.as_mut().poll(&mut context))).unwrap_err();
+
+    assert_eq!(
+        message_of(&*payload),
+        "blocking work was cancelled because the tokio runtime shut down"
+    );
+}
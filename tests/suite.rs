@@ -0,0 +1,136 @@
+mod common;
+
+use common::{events, panic_message, record};
+use spekt::Test;
+use std::{cell::Cell, future::Future, pin::Pin, sync::Arc};
+
+thread_local! {
+    static BROKEN_BEFORE_ALL: Cell<bool> = const { Cell::new(false) };
+    static BROKEN_AFTER_ALL: Cell<bool> = const { Cell::new(false) };
+}
+
+struct Database;
+
+#[spekt::async_trait]
+impl Test for Database {
+    type Error = anyhow::Error;
+
+    async fn before_all() -> Result<(), Self::Error> {
+        record("before_all");
+        anyhow::ensure!(!BROKEN_BEFORE_ALL.with(Cell::get), "migrations failed");
+
+        Ok(())
+    }
+
+    async fn before() -> Result<Self, Self::Error> {
+        record("before");
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        record("after");
+
+        Ok(())
+    }
+
+    async fn after_all() -> Result<(), Self::Error> {
+        record("after_all");
+        anyhow::ensure!(!BROKEN_AFTER_ALL.with(Cell::get), "failed to drop database");
+
+        Ok(())
+    }
+}
+
+type BoxFuture = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
+type Task = fn(Arc<Database>) -> BoxFuture;
+
+fn passing(_: Arc<Database>) -> BoxFuture {
+    Box::pin(async {
+        record("task");
+
+        Ok(())
+    })
+}
+
+fn failing(_: Arc<Database>) -> BoxFuture {
+    Box::pin(async {
+        record("task");
+
+        anyhow::bail!("missing row")
+    })
+}
+
+fn panicking(_: Arc<Database>) -> BoxFuture {
+    Box::pin(async {
+        record("task");
+
+        panic!("stray unwrap")
+    })
+}
+
+#[tokio::test]
+async fn suites_share_suite_hooks_between_per_task_lifecycles() {
+    Database::suite(vec![passing as Task, passing]).await;
+
+    assert_eq!(
+        events(),
+        [
+            "before_all",
+            "before",
+            "task",
+            "after",
+            "before",
+            "task",
+            "after",
+            "after_all"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn failing_before_all_runs_no_tasks() {
+    BROKEN_BEFORE_ALL.with(|broken| broken.set(true));
+
+    let message = panic_message(Database::suite(vec![passing as Task])).await;
+
+    assert_eq!(message, "migrations failed");
+    assert_eq!(events(), ["before_all"]);
+}
+
+#[tokio::test]
+async fn failing_tasks_are_reported_after_every_task_and_after_all_run() {
+    let message = panic_message(Database::suite(vec![failing as Task, passing, failing])).await;
+
+    assert_eq!(message, "missing row\nmissing row");
+    assert_eq!(events().last().unwrap(), "after_all");
+}
+
+#[tokio::test]
+async fn panicking_tasks_are_re_raised_after_every_task_and_after_all_run() {
+    let message = panic_message(Database::suite(vec![panicking as Task, passing])).await;
+
+    assert_eq!(message, "stray unwrap");
+    assert_eq!(
+        events(),
+        [
+            "before_all",
+            "before",
+            "task",
+            "after",
+            "before",
+            "task",
+            "after",
+            "after_all"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn failing_after_all_is_reported_with_task_failures() {
+    BROKEN_AFTER_ALL.with(|broken| broken.set(true));
+
+    let message = panic_message(Database::suite(vec![failing as Task])).await;
+
+    assert_eq!(message, "missing row\nfailed to drop database");
+}
@@ -0,0 +1,85 @@
+use spekt::{eventually, sleep, timeout};
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Waker},
+    time::{Duration, Instant},
+};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[test]
+fn sleeps_last_at_least_their_duration() {
+    let started = Instant::now();
+
+    block_on(sleep(Duration::from_millis(20)));
+
+    assert!(started.elapsed() >= Duration::from_millis(20));
+}
+
+#[test]
+fn sooner_deadlines_wake_before_later_ones() {
+    let started = Instant::now();
+    let mut later = Box::pin(sleep(Duration::from_secs(60)));
+
+    assert!(poll_once(&mut later).is_pending());
+
+    let result = block_on(timeout(
+        Duration::from_millis(10),
+        sleep(Duration::from_secs(60)),
+    ));
+
+    assert_eq!(result.unwrap_err().duration(), Duration::from_millis(10));
+    assert!(started.elapsed() < Duration::from_secs(30));
+}
+
+#[test]
+fn timeouts_pass_through_futures_that_finish_in_time() {
+    let result = block_on(timeout(Duration::from_secs(5), async {
+        sleep(Duration::from_millis(1)).await;
+
+        7
+    }));
+
+    assert_eq!(result, Ok(7));
+}
+
+#[test]
+fn eventually_fails_once_the_timeout_elapses() {
+    let result = block_on(eventually(
+        Duration::from_millis(20),
+        Duration::from_millis(5),
+        || async { false },
+    ));
+
+    assert_eq!(result.unwrap_err().duration(), Duration::from_millis(20));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn pending_sleeps_share_one_timer_thread() {
+    let threads = || std::fs::read_dir("/proc/self/task").unwrap().count();
+
+    block_on(sleep(Duration::from_millis(1)));
+
+    let before = threads();
+    let mut sleeps: Vec<_> = (0..100)
+        .map(|_| Box::pin(sleep(Duration::from_secs(60))))
+        .collect();
+
+    for sleep in &mut sleeps {
+        assert!(poll_once(sleep).is_pending());
+    }
+
+    // other tests in this binary may start threads of their own in the meantime, but far fewer than one per sleep
+    assert!(threads() < before + 10);
+}
+
+fn poll_once<F: Future + Unpin>(future: &mut F) -> std::task::Poll<F::Output> {
+    pin!(future).poll(&mut Context::from_waker(Waker::noop()))
+}
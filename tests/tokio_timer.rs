@@ -0,0 +1,141 @@
+#![cfg(feature = "tokio")]
+
+mod common;
+
+use common::panic_message;
+use spekt::{Test, TestConfig, TestOutcome};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+fn timed_out(config: TestConfig) -> impl std::future::Future<Output = TestOutcome<anyhow::Error>> {
+    Fixture::run_with_config(config, |_| std::future::pending())
+}
+
+#[test]
+fn timeouts_inside_runtimes_without_timers_ask_for_them() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let config = TestConfig::builder()
+        .test_timeout(Duration::from_millis(10))
+        .suppress_panic_output(true)
+        .build();
+
+    match runtime.block_on(timed_out(config)) {
+        TestOutcome::Panicked(message) => assert!(message.contains("enable_time"), "{}", message),
+        outcome => panic!("unexpected outcome {:?}", outcome),
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn timeouts_follow_the_runtime_clock() {
+    let config = TestConfig::builder()
+        .test_timeout(Duration::from_secs(60 * 60))
+        .build();
+    let started = std::time::Instant::now();
+
+    match timed_out(config).await {
+        TestOutcome::TimedOut(duration) => assert_eq!(duration, Duration::from_secs(60 * 60)),
+        outcome => panic!("unexpected outcome {:?}", outcome),
+    }
+
+    assert!(started.elapsed() < Duration::from_secs(60));
+}
+
+#[tokio::test]
+async fn tasks_within_their_timeout_pass() {
+    let config = TestConfig::builder()
+        .test_timeout(Duration::from_secs(5))
+        .build();
+    let outcome = Fixture::run_with_config(config, |_| async {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+
+        Ok(())
+    })
+    .await;
+
+    assert!(outcome.is_passed());
+}
+
+/// Handle to a child task that aborts it once the task owning the handle is dropped
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Spawn a child that ticks the counter every millisecond until it's stopped
+fn ticking(
+    ticks: &'static AtomicUsize,
+    stopped: impl Fn() -> bool + Send + 'static,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while !stopped() {
+            ticks.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    })
+}
+
+/// Check that a child has ticked, and doesn't tick anymore
+async fn assert_stopped(ticks: &AtomicUsize) {
+    let ticked = ticks.load(Ordering::SeqCst);
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    assert!(ticked > 0);
+    assert_eq!(ticks.load(Ordering::SeqCst), ticked);
+}
+
+#[tokio::test]
+async fn children_owned_by_a_timed_out_task_stop_at_the_deadline() {
+    static TICKS: AtomicUsize = AtomicUsize::new(0);
+
+    let config = TestConfig::builder()
+        .test_timeout(Duration::from_millis(20))
+        .build();
+    let outcome = Fixture::run_with_config(config, |_| async {
+        let _child = AbortOnDrop(ticking(&TICKS, || false));
+
+        std::future::pending().await
+    })
+    .await;
+
+    assert!(matches!(outcome, TestOutcome::TimedOut(_)));
+    assert_stopped(&TICKS).await;
+}
+
+#[tokio::test]
+async fn detached_children_watching_the_cancel_token_stop_at_the_deadline() {
+    static TICKS: AtomicUsize = AtomicUsize::new(0);
+
+    let message = panic_message(Fixture::test_with_deadline(
+        Duration::from_millis(20),
+        |context| async move {
+            let token = context.cancel_token().clone();
+            ticking(&TICKS, move || token.is_cancelled());
+
+            std::future::pending().await
+        },
+    ))
+    .await;
+
+    assert_eq!(message, "test() timed out after 20ms");
+    assert_stopped(&TICKS).await;
+}
@@ -0,0 +1,168 @@
+#![cfg(feature = "tracing")]
+
+use spekt::{CleanupStack, Test};
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+/// Subscriber collecting the spans first entered, the fields recorded on them, and the events emitted along with
+/// the span they were emitted in, in the order they happen
+#[derive(Clone, Default)]
+struct Collector {
+    names: Arc<Mutex<Vec<&'static str>>>,
+    entered: Arc<Mutex<HashSet<u64>>>,
+    current: Arc<Mutex<Vec<Id>>>,
+    records: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Collector {
+    fn name(&self, span: &Id) -> &'static str {
+        self.names.lock().unwrap()[span.into_u64() as usize - 1]
+    }
+
+    fn push(&self, record: String) {
+        self.records.lock().unwrap().push(record);
+    }
+
+    fn records(&self) -> Vec<String> {
+        self.records.lock().unwrap().clone()
+    }
+}
+
+/// Visitor keeping the message of an event, or the names of the fields recorded on a span
+#[derive(Default)]
+struct Fields(Vec<String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.0.push(format!("{:?}", value)),
+            name => self.0.push(name.to_string()),
+        }
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.names.lock().unwrap().push(span.metadata().name());
+
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut fields = Fields::default();
+        values.record(&mut fields);
+
+        for field in fields.0 {
+            self.push(format!("record {} on {}", field, self.name(span)));
+        }
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+
+        let span = match self.current.lock().unwrap().last() {
+            Some(span) => self.name(span),
+            None => "no span",
+        };
+
+        self.push(format!("{} in {}", fields.0.join(" "), span));
+    }
+
+    // spans are entered again every time their future is polled, and once more when it's dropped
+    fn enter(&self, span: &Id) {
+        self.current.lock().unwrap().push(span.clone());
+
+        if self.entered.lock().unwrap().insert(span.into_u64()) {
+            self.push(format!("enter {}", self.name(span)));
+        }
+    }
+
+    fn exit(&self, _: &Id) {
+        self.current.lock().unwrap().pop();
+    }
+}
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn env() -> Result<(), Self::Error> {
+        tracing::info!("env");
+
+        Ok(())
+    }
+
+    async fn before() -> Result<Self, Self::Error> {
+        unreachable!("built through before_with_cleanup")
+    }
+
+    async fn before_with_cleanup(
+        cleanup: &mut CleanupStack<Self::Error>,
+    ) -> Result<Self, Self::Error> {
+        tracing::info!("before");
+        cleanup.defer(|| async {
+            tracing::info!("cleanup");
+
+            Ok(())
+        });
+
+        Ok(Self)
+    }
+
+    async fn after(&self) -> Result<(), Self::Error> {
+        tracing::info!("after");
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn phases_run_inside_their_spans_in_order() {
+    let collector = Collector::default();
+    let _default = tracing::subscriber::set_default(collector.clone());
+
+    Fixture::test(|_| async {
+        tracing::info!("task");
+
+        Ok(())
+    })
+    .await;
+
+    assert_eq!(
+        collector.records(),
+        [
+            "enter spekt.before",
+            "env in spekt.before",
+            "before in spekt.before",
+            "record duration_ms on spekt.before",
+            "enter spekt.test",
+            "task in spekt.test",
+            "record duration_ms on spekt.test",
+            "enter spekt.after",
+            "after in spekt.after",
+            "record duration_ms on spekt.after",
+            "cleanup in no span",
+        ]
+    );
+}
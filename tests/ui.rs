@@ -0,0 +1,17 @@
+#![cfg(feature = "macros")]
+
+#[test]
+fn test_attribute() {
+    let cases = trybuild::TestCases::new();
+
+    cases.pass("tests/ui/test/pass.rs");
+    cases.compile_fail("tests/ui/test/[!p]*.rs");
+}
+
+#[test]
+fn test_derive() {
+    let cases = trybuild::TestCases::new();
+
+    cases.pass("tests/ui/derive/pass.rs");
+    cases.compile_fail("tests/ui/derive/[!p]*.rs");
+}
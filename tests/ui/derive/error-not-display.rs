@@ -0,0 +1,9 @@
+use spekt::Test;
+
+struct Error;
+
+#[derive(Default, Test)]
+#[spekt(error = "Error")]
+struct Fixture;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use spekt::Test;
+
+#[derive(Test)]
+struct Fixture {
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,53 @@
+use spekt::Test;
+
+#[derive(Default, Test)]
+struct Defaulted;
+
+#[derive(Debug)]
+struct Error;
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str("broken")
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Default, Test)]
+#[spekt(error = "Error")]
+struct CustomError;
+
+async fn connect() -> Result<Connected, anyhow::Error> {
+    Ok(Connected { port: 5432 })
+}
+
+#[derive(Test)]
+#[spekt(before = connect)]
+struct Connected {
+    port: u16,
+}
+
+#[derive(Test)]
+#[spekt(error = "Error", before = Generic::build)]
+struct Generic<T: Send + Sync + 'static> {
+    value: Option<T>,
+}
+
+impl<T: Send + Sync + 'static> Generic<T> {
+    async fn build() -> Result<Self, Error> {
+        Ok(Self { value: None })
+    }
+}
+
+fn assert_error<T: Test<Error = E>, E>() {}
+
+fn main() {
+    assert_error::<Defaulted, anyhow::Error>();
+    assert_error::<CustomError, Error>();
+    assert_error::<Connected, anyhow::Error>();
+    assert_error::<Generic<u8>, Error>();
+
+    let _ = Connected { port: 0 }.port;
+    let _ = Generic::<u8> { value: None }.value;
+}
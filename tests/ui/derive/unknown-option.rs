@@ -0,0 +1,7 @@
+use spekt::Test;
+
+#[derive(Default, Test)]
+#[spekt(after = teardown)]
+struct Fixture;
+
+fn main() {}
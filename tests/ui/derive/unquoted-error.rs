@@ -0,0 +1,7 @@
+use spekt::Test;
+
+#[derive(Default, Test)]
+#[spekt(error = std::io::Error)]
+struct Fixture;
+
+fn main() {}
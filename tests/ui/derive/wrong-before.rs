@@ -0,0 +1,11 @@
+use spekt::Test;
+
+fn connect() -> Result<Fixture, anyhow::Error> {
+    Ok(Fixture)
+}
+
+#[derive(Test)]
+#[spekt(before = connect)]
+struct Fixture;
+
+fn main() {}
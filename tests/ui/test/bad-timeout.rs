@@ -0,0 +1,29 @@
+use spekt::Test;
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[spekt::test(timeout = "5h")]
+async fn bad_unit(_fixture: std::sync::Arc<Fixture>) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+#[spekt::test(timeout = "soon")]
+async fn no_amount(_fixture: std::sync::Arc<Fixture>) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+#[spekt::test(timeout = 5)]
+async fn not_a_string(_fixture: std::sync::Arc<Fixture>) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use spekt::Test;
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[spekt::test]
+async fn no_arguments() -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use spekt::Test;
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[spekt::test]
+async fn plain(_fixture: std::sync::Arc<Fixture>) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+#[spekt::test(timeout = "5s")]
+async fn with_timeout(_fixture: std::sync::Arc<Fixture>) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+#[spekt::test(retries = 3)]
+async fn with_retries(_fixture: std::sync::Arc<Fixture>) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+#[spekt::test(timeout = "500ms", retries = 1)]
+async fn with_timeout_and_retries(_fixture: std::sync::Arc<Fixture>) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+#[spekt::test]
+async fn qualified(_fixture: std::sync::Arc<Fixture>) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use spekt::Test;
+
+struct Fixture;
+
+#[spekt::async_trait]
+impl Test for Fixture {
+    type Error = anyhow::Error;
+
+    async fn before() -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[spekt::test]
+async fn two_arguments(
+    _fixture: std::sync::Arc<Fixture>,
+    _other: std::sync::Arc<Fixture>,
+) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+fn main() {}